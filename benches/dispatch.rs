@@ -0,0 +1,58 @@
+use std::any::Any;
+use std::rc::Rc;
+use std::cell::RefCell;
+use criterion::{BenchmarkId, Criterion, Throughput, black_box, criterion_group, criterion_main};
+use easy_events::{Event, implement_event};
+use easy_signals::{EventBus, SignalObserver, SignalSubject};
+
+const OBSERVER_COUNTS: [usize; 4] = [1, 10, 100, 1000];
+
+struct Tick;
+implement_event!(Tick, "tick");
+
+struct NullObserver;
+
+impl SignalObserver for NullObserver {
+    fn process_signal(&mut self, event: Rc<dyn Event>) {
+        black_box(event);
+    }
+}
+
+fn bus_with_observers(count: usize) -> Rc<RefCell<EventBus>> {
+    let bus = EventBus::new();
+    for _ in 0..count {
+        bus.borrow_mut().subscribe_observer(Rc::new(RefCell::new(NullObserver)));
+    }
+    bus
+}
+
+fn send_signal(c: &mut Criterion) {
+    let event: Rc<dyn Event> = Rc::new(Tick);
+    let mut group = c.benchmark_group("send_signal");
+    for count in OBSERVER_COUNTS {
+        let bus = bus_with_observers(count);
+        group.throughput(Throughput::Elements(count as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(count), &bus, |b, bus| {
+            b.iter(|| bus.borrow().send_signal(event.clone()))
+        });
+    }
+    group.finish();
+}
+
+fn snapshot(c: &mut Criterion) {
+    let event: Rc<dyn Event> = Rc::new(Tick);
+    let mut group = c.benchmark_group("snapshot");
+    for count in OBSERVER_COUNTS {
+        let bus = bus_with_observers(count);
+        group.bench_with_input(BenchmarkId::new("copy_observers", count), &bus, |b, bus| {
+            b.iter(|| bus.borrow().copy_observers())
+        });
+        group.bench_with_input(BenchmarkId::new("get_signal_snapshot", count), &bus, |b, bus| {
+            b.iter(|| bus.borrow().get_signal_snapshot(event.clone()))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, send_signal, snapshot);
+criterion_main!(benches);
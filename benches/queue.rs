@@ -0,0 +1,60 @@
+use std::any::Any;
+use std::rc::Rc;
+use std::cell::RefCell;
+use criterion::{BenchmarkId, Criterion, Throughput, black_box, criterion_group, criterion_main};
+use easy_events::{Event, implement_event};
+use easy_signals::{EventBus, SignalObserver, SignalQueue, SignalSubject};
+
+const OBSERVER_COUNTS: [usize; 4] = [1, 10, 100, 1000];
+const SIGNALS: usize = 1000;
+
+struct Tick;
+implement_event!(Tick, "tick");
+
+struct NullObserver;
+
+impl SignalObserver for NullObserver {
+    fn process_signal(&mut self, event: Rc<dyn Event>) {
+        black_box(event);
+    }
+}
+
+fn push_pop(c: &mut Criterion) {
+    let bus = EventBus::new();
+    let queue = SignalQueue::new();
+    let mut group = c.benchmark_group("signal_queue");
+    group.throughput(Throughput::Elements(SIGNALS as u64));
+    group.bench_function("push_drain", |b| {
+        b.iter(|| {
+            for _ in 0..SIGNALS {
+                queue.push(bus.borrow().get_signal_snapshot(Rc::new(Tick)));
+            }
+            queue.drain()
+        })
+    });
+    group.finish();
+}
+
+fn process_all(c: &mut Criterion) {
+    let mut group = c.benchmark_group("signal_queue/process_all");
+    for count in OBSERVER_COUNTS {
+        let bus = EventBus::new();
+        for _ in 0..count {
+            bus.borrow_mut().subscribe_observer(Rc::new(RefCell::new(NullObserver)));
+        }
+        let queue = SignalQueue::new();
+        group.throughput(Throughput::Elements((SIGNALS * count) as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(count), &bus, |b, bus| {
+            b.iter(|| {
+                for _ in 0..SIGNALS {
+                    queue.push(bus.borrow().get_signal_snapshot(Rc::new(Tick)));
+                }
+                queue.process_all()
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, push_pop, process_all);
+criterion_main!(benches);
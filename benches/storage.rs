@@ -0,0 +1,69 @@
+use std::rc::Rc;
+use std::cell::RefCell;
+use criterion::{BenchmarkId, Criterion, black_box, criterion_group, criterion_main};
+use easy_events::Event;
+use easy_signals::{SignalObserver, SignalSubject, SmallObserverList, implement_signal_subject};
+
+/// Around the four observers `SmallObserverList` holds inline.
+const OBSERVER_COUNTS: [usize; 4] = [1, 2, 4, 8];
+
+struct NullObserver;
+
+impl SignalObserver for NullObserver {
+    fn process_signal(&mut self, event: Rc<dyn Event>) {
+        black_box(event);
+    }
+}
+
+#[derive(Default)]
+struct VecSubject {
+    subs: Vec<Rc<RefCell<dyn SignalObserver>>>
+}
+
+implement_signal_subject!(VecSubject, subs);
+
+#[derive(Default)]
+struct SmallSubject {
+    subs: SmallObserverList
+}
+
+implement_signal_subject!(SmallSubject, subs);
+
+fn observers(count: usize) -> Vec<Rc<RefCell<dyn SignalObserver>>> {
+    (0..count)
+        .map(|_| Rc::new(RefCell::new(NullObserver)) as Rc<RefCell<dyn SignalObserver>>)
+        .collect()
+}
+
+/// Builds a subject and subscribes `count` observers, the cost a
+/// fine-grained subject pays each time one is created. Duplicates are allowed
+/// so the identity checks of `subscribe_observer` do not drown out the cost
+/// of the storage itself.
+fn subscribe(c: &mut Criterion) {
+    let mut group = c.benchmark_group("storage_subscribe");
+    for count in OBSERVER_COUNTS {
+        let observers = observers(count);
+        group.bench_with_input(BenchmarkId::new("vec", count), &observers, |b, observers| {
+            b.iter(|| {
+                let mut subject = VecSubject::default();
+                for observer in observers {
+                    subject.subscribe_observer_allow_duplicates(observer.clone());
+                }
+                subject
+            })
+        });
+        group.bench_with_input(BenchmarkId::new("smallvec", count), &observers, |b, observers| {
+            b.iter(|| {
+                let mut subject = SmallSubject::default();
+                for observer in observers {
+                    subject.subscribe_observer_allow_duplicates(observer.clone());
+                }
+                subject
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, subscribe);
+criterion_main!(benches);
@@ -0,0 +1,153 @@
+use proc_macro::{Delimiter, TokenStream, TokenTree};
+
+/// Derives `easy_signals::SignalSubject` for a struct with one field marked
+/// `#[observers]`, holding a `Vec<Rc<RefCell<dyn SignalObserver>>>`.
+///
+/// Expands to the same impl as `easy_signals::implement_signal_subject!`. If
+/// `easy_signals` is renamed in `Cargo.toml`, name it on the struct with
+/// `#[signal_subject(crate = renamed)]`.
+#[proc_macro_derive(SignalSubject, attributes(observers, signal_subject))]
+pub fn derive_signal_subject(input: TokenStream) -> TokenStream {
+    match expand(input) {
+        Ok(output) => output,
+        Err(message) => format!("::core::compile_error!({:?});", message).parse().unwrap()
+    }
+}
+
+fn expand(input: TokenStream) -> Result<TokenStream, String> {
+    let mut tokens = input.into_iter().peekable();
+    let mut name = None;
+    let mut body = None;
+    let mut crate_path = None;
+
+    while let Some(token) = tokens.next() {
+        match token {
+            TokenTree::Punct(punct) if punct.as_char() == '#' => {
+                if let Some(TokenTree::Group(group)) = tokens.next() {
+                    if let Some(path) = signal_subject_attribute(group.stream())? {
+                        if crate_path.is_some() {
+                            return Err("only one #[signal_subject(crate = ..)] attribute is allowed".into());
+                        }
+                        crate_path = Some(path);
+                    }
+                }
+            }
+            TokenTree::Ident(ident) if ident.to_string() == "struct" => {
+                match tokens.next() {
+                    Some(TokenTree::Ident(ident)) => name = Some(ident.to_string()),
+                    _ => return Err("expected a struct name".into())
+                }
+                match tokens.next() {
+                    Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Brace => {
+                        body = Some(group.stream());
+                    }
+                    Some(TokenTree::Punct(punct)) if punct.as_char() == '<' => {
+                        return Err("#[derive(SignalSubject)] does not support generic structs".into());
+                    }
+                    _ => return Err("#[derive(SignalSubject)] requires a struct with named fields".into())
+                }
+                break;
+            }
+            TokenTree::Ident(ident) if ident.to_string() == "enum" || ident.to_string() == "union" => {
+                return Err("#[derive(SignalSubject)] can only be used on structs".into());
+            }
+            _ => {}
+        }
+    }
+
+    let name = name.ok_or("#[derive(SignalSubject)] can only be used on structs")?;
+    let field = observers_field(body.unwrap())?;
+
+    let crate_path = crate_path.unwrap_or_else(|| "::easy_signals".into());
+    Ok(format!("{}::implement_signal_subject!({}, {});", crate_path, name, field).parse().unwrap())
+}
+
+/// Reads the crate path out of `#[signal_subject(crate = path)]`, or returns
+/// `None` for any other attribute.
+fn signal_subject_attribute(attribute: TokenStream) -> Result<Option<String>, String> {
+    let mut tokens = attribute.into_iter();
+    match tokens.next() {
+        Some(TokenTree::Ident(ident)) if ident.to_string() == "signal_subject" => {}
+        _ => return Ok(None)
+    }
+    let malformed = || "expected #[signal_subject(crate = path)]".to_string();
+    let args = match (tokens.next(), tokens.next()) {
+        (Some(TokenTree::Group(group)), None) if group.delimiter() == Delimiter::Parenthesis => group.stream(),
+        _ => return Err(malformed())
+    };
+    let mut args = args.into_iter();
+    match (args.next(), args.next()) {
+        (Some(TokenTree::Ident(key)), Some(TokenTree::Punct(eq))) if key.to_string() == "crate" && eq.as_char() == '=' => {}
+        _ => return Err(malformed())
+    }
+    let path: TokenStream = args.collect();
+    if path.is_empty() {
+        return Err(malformed());
+    }
+    Ok(Some(path.to_string()))
+}
+
+fn observers_field(body: TokenStream) -> Result<String, String> {
+    let mut found = None;
+    let mut marked = false;
+    let mut field_name = None;
+    let mut in_type = false;
+    let mut angle_depth = 0usize;
+    let mut after_dash = false;
+    let mut tokens = body.into_iter().peekable();
+
+    while let Some(token) = tokens.next() {
+        let is_dash = matches!(&token, TokenTree::Punct(punct) if punct.as_char() == '-');
+        match token {
+            TokenTree::Punct(punct) if punct.as_char() == '#' && !in_type => {
+                if let Some(TokenTree::Group(group)) = tokens.next() {
+                    if is_observers_attribute(group.stream()) {
+                        marked = true;
+                    }
+                }
+            }
+            TokenTree::Ident(ident) if !in_type => {
+                let ident = ident.to_string();
+                if ident != "pub" {
+                    field_name = Some(ident);
+                }
+            }
+            TokenTree::Punct(punct) if punct.as_char() == ':' && !in_type => {
+                in_type = true;
+            }
+            TokenTree::Punct(punct) if punct.as_char() == '<' && in_type => angle_depth += 1,
+            TokenTree::Punct(punct) if punct.as_char() == '>' && in_type && !after_dash => {
+                angle_depth = angle_depth.saturating_sub(1);
+            }
+            TokenTree::Punct(punct) if punct.as_char() == ',' && in_type && angle_depth == 0 => {
+                if marked {
+                    if found.is_some() {
+                        return Err("only one field may be marked #[observers]".into());
+                    }
+                    found = field_name.take();
+                }
+                marked = false;
+                field_name = None;
+                in_type = false;
+            }
+            _ => {}
+        }
+        after_dash = is_dash;
+    }
+    if marked {
+        if found.is_some() {
+            return Err("only one field may be marked #[observers]".into());
+        }
+        found = field_name;
+    }
+
+    found.ok_or_else(|| "#[derive(SignalSubject)] requires a field marked #[observers]".into())
+}
+
+fn is_observers_attribute(attribute: TokenStream) -> bool {
+    let mut tokens = attribute.into_iter();
+    matches!(
+        (tokens.next(), tokens.next()),
+        (Some(TokenTree::Ident(ident)), None) if ident.to_string() == "observers"
+    )
+}
@@ -0,0 +1,214 @@
+//! Counterparts of `SignalObserver` and `SignalSubject` for observers that
+//! need to `.await` while handling a signal. The module is not named `async`
+//! only because that is a keyword.
+//!
+//! Like the crate root, this is single-threaded and built on `Rc`, so it suits
+//! local executors; the crate does not depend on any particular one. Observers
+//! take `&self` and keep their state behind `Cell` or `RefCell`, so the
+//! subject holds no borrow across an `.await` and `send_signal_concurrent`
+//! can run several handlers at once. Dispatch hooks do not run for these
+//! subjects.
+
+use alloc::boxed::Box;
+use alloc::rc::Rc;
+use alloc::vec::Vec;
+use core::future::{Future, poll_fn};
+use core::pin::Pin;
+use core::slice::Iter;
+use core::task::Poll;
+use easy_events::Event;
+
+/// The future returned by `AsyncSignalObserver::process_signal`.
+pub type SignalFuture<'a> = Pin<Box<dyn Future<Output = ()> + 'a>>;
+
+pub trait AsyncSignalObserver {
+    fn process_signal(&self, event: Rc<dyn Event>) -> SignalFuture<'_>;
+}
+
+pub trait AsyncSignalSubject {
+    fn get_observers_iter(&self) -> Iter<'_, Rc<dyn AsyncSignalObserver>>;
+    fn get_observers_mut(&mut self) -> &mut Vec<Rc<dyn AsyncSignalObserver>>;
+    /// Subscribes an observer at the end of the dispatch order. Subscribing
+    /// it again keeps its original position.
+    fn subscribe_observer(&mut self, new_observer: Rc<dyn AsyncSignalObserver>) {
+        let observers = self.get_observers_mut();
+        if !observers.iter().any(|x| same_observer(x, &new_observer)) {
+            observers.push(new_observer);
+        }
+    }
+    fn unsubscribe_observer(&mut self, observer: &Rc<dyn AsyncSignalObserver>) -> bool {
+        let observers = self.get_observers_mut();
+        if let Some(index) = observers.iter().position(|x| same_observer(x, observer)) {
+            observers.remove(index);
+            true
+        } else {
+            false
+        }
+    }
+    fn observer_count(&self) -> usize {
+        self.get_observers_iter().len()
+    }
+    fn copy_observers(&self) -> Vec<Rc<dyn AsyncSignalObserver>> {
+        self.get_observers_iter().cloned().collect()
+    }
+    /// Awaits each observer in turn, in subscription order, and resolves to
+    /// how many were notified. The observer list is copied when this is
+    /// called, so the subject is not borrowed while the future runs.
+    fn send_signal(&self, event: Rc<dyn Event>) -> impl Future<Output = usize>
+    where
+        Self: Sized
+    {
+        let observers = self.copy_observers();
+        async move {
+            for o in observers.iter() {
+                o.process_signal(event.clone()).await;
+            }
+            observers.len()
+        }
+    }
+    /// Like `send_signal`, but runs every observer's handler at once,
+    /// resolving when all of them have finished. Handlers are started in
+    /// subscription order.
+    fn send_signal_concurrent(&self, event: Rc<dyn Event>) -> impl Future<Output = usize>
+    where
+        Self: Sized
+    {
+        let observers = self.copy_observers();
+        async move {
+            let mut pending: Vec<Option<SignalFuture<'_>>> = observers.iter()
+                .map(|o| Some(o.process_signal(event.clone())))
+                .collect();
+            poll_fn(|cx| {
+                let mut finished = true;
+                for slot in pending.iter_mut() {
+                    if let Some(future) = slot {
+                        if future.as_mut().poll(cx).is_ready() {
+                            *slot = None;
+                        } else {
+                            finished = false;
+                        }
+                    }
+                }
+                if finished { Poll::Ready(()) } else { Poll::Pending }
+            }).await;
+            observers.len()
+        }
+    }
+}
+
+fn same_observer(a: &Rc<dyn AsyncSignalObserver>, b: &Rc<dyn AsyncSignalObserver>) -> bool {
+    Rc::as_ptr(a) as *const () == Rc::as_ptr(b) as *const ()
+}
+
+/// Implements `AsyncSignalSubject` for a struct whose `$observers_field` is a
+/// `Vec<Rc<dyn AsyncSignalObserver>>`.
+#[macro_export]
+macro_rules! implement_async_signal_subject {
+    (
+        $struct:ident,
+        $observers_field:ident $(,)?
+    ) => {
+        impl $crate::asynchronous::AsyncSignalSubject for $struct {
+            fn get_observers_iter(&self) -> ::core::slice::Iter<'_, $crate::__private::Rc<dyn $crate::asynchronous::AsyncSignalObserver>> {
+                self.$observers_field.iter()
+            }
+
+            fn get_observers_mut(&mut self) -> &mut $crate::__private::Vec<$crate::__private::Rc<dyn $crate::asynchronous::AsyncSignalObserver>> {
+                &mut self.$observers_field
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::any::Any;
+    use std::cell::{Cell, RefCell};
+    use std::task::{Context, Waker};
+    use easy_events::implement_event;
+
+    #[derive(Default)]
+    struct Subject {
+        subs: Vec<Rc<dyn AsyncSignalObserver>>
+    }
+
+    implement_async_signal_subject!(Subject, subs);
+
+    struct EventA;
+    implement_event!(EventA, "event_a");
+
+    /// Returns `Pending` once before completing, so a handler yields to the
+    /// executor partway through.
+    struct YieldOnce(bool);
+
+    impl Future for YieldOnce {
+        type Output = ();
+
+        fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+            if self.0 {
+                Poll::Ready(())
+            } else {
+                self.0 = true;
+                cx.waker().wake_by_ref();
+                Poll::Pending
+            }
+        }
+    }
+
+    struct StepObserver {
+        id: u32,
+        calls: Cell<u32>,
+        log: Rc<RefCell<Vec<(u32, bool)>>>
+    }
+
+    impl StepObserver {
+        fn new(id: u32, log: &Rc<RefCell<Vec<(u32, bool)>>>) -> Rc<Self> {
+            Rc::new(Self { id, calls: Cell::new(0), log: log.clone() })
+        }
+    }
+
+    impl AsyncSignalObserver for StepObserver {
+        fn process_signal(&self, _event: Rc<dyn Event>) -> SignalFuture<'_> {
+            Box::pin(async move {
+                self.log.borrow_mut().push((self.id, false));
+                YieldOnce(false).await;
+                self.calls.set(self.calls.get() + 1);
+                self.log.borrow_mut().push((self.id, true));
+            })
+        }
+    }
+
+    fn block_on<F: Future>(future: F) -> F::Output {
+        let mut future = std::pin::pin!(future);
+        let mut cx = Context::from_waker(Waker::noop());
+        loop {
+            if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+                return output;
+            }
+        }
+    }
+
+    #[test]
+    fn async_send_signal_test() {
+        let mut subject = Subject::default();
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let first = StepObserver::new(1, &log);
+        subject.subscribe_observer(first.clone());
+        subject.subscribe_observer(StepObserver::new(2, &log));
+        subject.subscribe_observer(first.clone());
+        assert_eq!(subject.observer_count(), 2);
+
+        assert_eq!(block_on(subject.send_signal(Rc::new(EventA{}))), 2);
+        assert_eq!(*log.borrow(), vec![(1, false), (1, true), (2, false), (2, true)]);
+
+        log.borrow_mut().clear();
+        assert_eq!(block_on(subject.send_signal_concurrent(Rc::new(EventA{}))), 2);
+        assert_eq!(*log.borrow(), vec![(1, false), (2, false), (1, true), (2, true)]);
+        assert_eq!(first.calls.get(), 2);
+
+        let first_dyn: Rc<dyn AsyncSignalObserver> = first;
+        assert!(subject.unsubscribe_observer(&first_dyn));
+        assert_eq!(block_on(subject.send_signal(Rc::new(EventA{}))), 1);
+    }
+}
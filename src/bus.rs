@@ -0,0 +1,45 @@
+use alloc::rc::Rc;
+use alloc::vec::Vec;
+use core::cell::RefCell;
+use crate::{SignalObserver, implement_signal_subject};
+
+/// A ready-made subject that only holds its observers, for when a custom
+/// subject type isn't needed. Everything else comes from `SignalSubject`.
+#[derive(Default)]
+pub struct EventBus {
+    subs: Vec<Rc<RefCell<dyn SignalObserver>>>
+}
+
+implement_signal_subject!(EventBus, subs);
+
+impl EventBus {
+    pub fn new() -> Rc<RefCell<Self>> {
+        Rc::new(RefCell::new(Self::default()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::any::Any;
+    use std::cell::Cell;
+    use easy_events::{Event, implement_event};
+    use crate::{ClosureObserver, SignalSubject};
+
+    struct EventA;
+    implement_event!(EventA, "event_a");
+
+    #[test]
+    fn event_bus_test() {
+        let bus = EventBus::new();
+        let received = Rc::new(Cell::new(0));
+
+        let counter = received.clone();
+        bus.borrow_mut().subscribe_observer(ClosureObserver::new(move |_event| {
+            counter.set(counter.get() + 1);
+        }));
+
+        assert_eq!(bus.borrow().get_signal_snapshot(Rc::new(EventA{})).execute(), 1);
+        assert_eq!(received.get(), 1);
+    }
+}
@@ -0,0 +1,126 @@
+use alloc::rc::Rc;
+use alloc::vec::Vec;
+use core::cell::RefCell;
+use easy_events::Event;
+use crate::{Origin, SignalObserver, SignalQueue, SignalSnapShot, append_unique_observers, dispatch_signal, same_observer};
+
+/// A signal being put together by `SignalSubject::signal`, which combines the
+/// targeting options of the `send_signal_*` family. The audience starts as
+/// the subject's observers when `signal` was called, and each method adjusts
+/// it in the order they are called. Nothing is sent until `send`.
+#[must_use = "a signal is only sent by calling `send`"]
+pub struct SignalEmitter<'q> {
+    event: Rc<dyn Event>,
+    subs: Vec<Rc<RefCell<dyn SignalObserver>>>,
+    queue: Option<&'q SignalQueue>,
+    origin: Origin
+}
+
+impl<'q> SignalEmitter<'q> {
+    pub(crate) fn new(origin: Origin, event: Rc<dyn Event>, subs: Vec<Rc<RefCell<dyn SignalObserver>>>) -> Self {
+        Self {
+            event,
+            subs,
+            queue: None,
+            origin
+        }
+    }
+
+    /// Adds `targets` after the current audience, as in
+    /// `SignalSubject::send_signal_to`.
+    pub fn to(mut self, targets: &[Rc<RefCell<dyn SignalObserver>>]) -> Self {
+        append_unique_observers(&mut self.subs, targets);
+        self
+    }
+
+    /// Removes `excluded` from the current audience, as in
+    /// `SignalSubject::send_signal_except`.
+    pub fn except(mut self, excluded: &[Rc<RefCell<dyn SignalObserver>>]) -> Self {
+        self.subs.retain(|x| !excluded.iter().any(|e| same_observer(x, e)));
+        self
+    }
+
+    /// Keeps only the observers in the current audience for which `predicate`
+    /// returns true, as in `SignalSubject::send_signal_where`. It is named
+    /// `filter` because `where` is a keyword, and `where_` reads poorly in a
+    /// chain.
+    pub fn filter<F: Fn(&Rc<RefCell<dyn SignalObserver>>) -> bool>(mut self, predicate: F) -> Self {
+        self.subs.retain(|x| predicate(x));
+        self
+    }
+
+    /// Makes `send` push a snapshot of the signal onto `queue` rather than
+    /// delivering it.
+    pub fn deferred(mut self, queue: &'q SignalQueue) -> Self {
+        self.queue = Some(queue);
+        self
+    }
+
+    /// Delivers the signal and returns how many observers were notified. A
+    /// `deferred` signal is queued instead, so this returns 0; on a bounded
+    /// queue it may be dropped under the queue's `OverflowPolicy`.
+    pub fn send(self) -> usize {
+        match self.queue {
+            Some(queue) => {
+                queue.push(SignalSnapShot::from_subject(self.origin, self.event, self.subs));
+                0
+            }
+            None => dispatch_signal(self.origin, &self.subs, self.event)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::any::Any;
+    use easy_events::implement_event;
+    use crate::{EventBus, SignalSubject};
+
+    struct EventA;
+    implement_event!(EventA, "event_a");
+
+    struct OrderObserver {
+        id: u32,
+        log: Rc<RefCell<Vec<u32>>>
+    }
+
+    impl SignalObserver for OrderObserver {
+        fn process_signal(&mut self, _event: Rc<dyn Event>) {
+            self.log.borrow_mut().push(self.id);
+        }
+    }
+
+    fn order_observer(id: u32, log: &Rc<RefCell<Vec<u32>>>) -> Rc<RefCell<dyn SignalObserver>> {
+        Rc::new(RefCell::new(OrderObserver { id, log: log.clone() }))
+    }
+
+    #[test]
+    fn signal_builder_test() {
+        let bus = EventBus::new();
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let first = order_observer(1, &log);
+        let second = order_observer(2, &log);
+        bus.borrow_mut().subscribe_observer(first.clone());
+        bus.borrow_mut().subscribe_observer(second.clone());
+        let target = order_observer(3, &log);
+
+        let sent = bus.borrow().signal(Rc::new(EventA{}))
+            .to(std::slice::from_ref(&target))
+            .except(std::slice::from_ref(&first))
+            .send();
+        assert_eq!(sent, 2);
+        assert_eq!(*log.borrow(), vec![2, 3]);
+
+        log.borrow_mut().clear();
+        let queue = SignalQueue::new();
+        let sent = bus.borrow().signal(Rc::new(EventA{}))
+            .filter(|x| !Rc::ptr_eq(x, &second))
+            .deferred(&queue)
+            .send();
+        assert_eq!(sent, 0);
+        assert!(log.borrow().is_empty());
+        assert_eq!(queue.process_all(), 1);
+        assert_eq!(*log.borrow(), vec![1]);
+    }
+}
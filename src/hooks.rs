@@ -0,0 +1,333 @@
+//! Thread-local hooks that apply to every signal dispatched on the current
+//! thread, whichever subject or snapshot sends it, and the process-wide event
+//! names used to show events in `Debug` output and trace logs. The `sync`
+//! module does not use these hooks.
+
+use std::rc::Rc;
+use std::any::TypeId;
+use std::cell::{Cell, RefCell};
+use std::collections::BTreeMap;
+use std::sync::{Mutex, PoisonError};
+use easy_events::Event;
+use crate::{EventName, Origin};
+
+type Interceptor = Rc<dyn Fn(&Rc<dyn Event>)>;
+
+thread_local! {
+    static INTERCEPTOR: RefCell<Option<Interceptor>> = const { RefCell::new(None) };
+    static UNHANDLED: RefCell<Option<Interceptor>> = const { RefCell::new(None) };
+    static DEPTH: Cell<usize> = const { Cell::new(0) };
+    static MAX_DEPTH: Cell<Option<usize>> = const { Cell::new(None) };
+    static SENDING: RefCell<Vec<(Origin, *const ())>> = const { RefCell::new(Vec::new()) };
+}
+
+static EVENT_NAMES: Mutex<BTreeMap<TypeId, &'static str>> = Mutex::new(BTreeMap::new());
+
+/// Registers a callback that sees each event just before it is dispatched,
+/// replacing any previous interceptor on this thread.
+pub fn set_interceptor<F: Fn(&Rc<dyn Event>) + 'static>(interceptor: F) {
+    INTERCEPTOR.with(|i| *i.borrow_mut() = Some(Rc::new(interceptor)));
+}
+
+pub fn clear_interceptor() {
+    INTERCEPTOR.with(|i| *i.borrow_mut() = None);
+}
+
+/// Registers a callback that sees each event a dispatch delivered to no
+/// observer at all, which often points to a missing subscription. Replaces any
+/// previous handler on this thread. While no handler is set, unhandled events
+/// cost nothing beyond a thread-local lookup.
+pub fn set_unhandled_handler<F: Fn(&Rc<dyn Event>) + 'static>(handler: F) {
+    UNHANDLED.with(|u| *u.borrow_mut() = Some(Rc::new(handler)));
+}
+
+pub fn clear_unhandled_handler() {
+    UNHANDLED.with(|u| *u.borrow_mut() = None);
+}
+
+/// Limits how deeply dispatches may nest on this thread, such as an observer
+/// that sends a signal from `process_signal`, which sends another, and so on.
+/// A dispatch that would exceed `depth` panics instead of recursing until the
+/// stack overflows. A depth of 1 disallows any nesting.
+pub fn set_max_dispatch_depth(depth: usize) {
+    MAX_DEPTH.with(|m| m.set(Some(depth)));
+}
+
+pub fn clear_max_dispatch_depth() {
+    MAX_DEPTH.with(|m| m.set(None));
+}
+
+/// How many dispatches are currently in progress on this thread.
+pub fn dispatch_depth() -> usize {
+    DEPTH.with(|d| d.get())
+}
+
+/// Registers the type name of `E` for every thread, so the `Debug` impls of
+/// snapshots and queues and the `log` trace records show events of type `E`
+/// by name rather than by `TypeId`.
+///
+/// Names have to be registered because events are handled as `dyn Event`,
+/// and `std::any::type_name` can only name the concrete type where it is
+/// still known.
+pub fn register_event_name<E: Event + 'static>() {
+    EVENT_NAMES.lock().unwrap_or_else(PoisonError::into_inner).insert(TypeId::of::<E>(), std::any::type_name::<E>());
+}
+
+/// The name registered for the concrete type of `event`, if any.
+pub fn event_name(event: &dyn Event) -> Option<&'static str> {
+    registered_name(event.as_any().type_id())
+}
+
+pub(crate) fn registered_name(id: TypeId) -> Option<&'static str> {
+    EVENT_NAMES.lock().unwrap_or_else(PoisonError::into_inner).get(&id).copied()
+}
+
+/// Tracks one dispatch in progress, for as long as it is alive.
+pub(crate) struct DispatchGuard;
+
+impl Drop for DispatchGuard {
+    fn drop(&mut self) {
+        DEPTH.with(|d| d.set(d.get() - 1));
+    }
+}
+
+/// Called at the start of every dispatch, before any observer is notified.
+pub(crate) fn begin_dispatch(event: &Rc<dyn Event>, observers: usize) -> DispatchGuard {
+    let depth = DEPTH.with(|d| {
+        d.set(d.get() + 1);
+        d.get()
+    });
+    let guard = DispatchGuard;
+    if let Some(max) = MAX_DEPTH.with(|m| m.get()) {
+        if depth > max {
+            panic!("signal dispatch exceeded the maximum depth of {}", max);
+        }
+    }
+    trace!(target: "easy_signals::dispatch", "dispatching {:?} to {} observers at depth {}", EventName::of(&**event), observers, depth);
+    intercept(event);
+    guard
+}
+
+/// Marks a subject as sending an event, for as long as it is alive.
+pub(crate) struct SendGuard {
+    key: (Origin, *const ())
+}
+
+impl Drop for SendGuard {
+    fn drop(&mut self) {
+        SENDING.with(|s| {
+            let mut sending = s.borrow_mut();
+            if let Some(index) = sending.iter().rposition(|x| *x == self.key) {
+                sending.swap_remove(index);
+            }
+        });
+    }
+}
+
+/// Called when the subject `origin` starts sending `event`. Returns `None` if
+/// the same subject is already sending the same event further up the stack,
+/// meaning the signal has looped back on itself and should go no further.
+pub(crate) fn begin_send(origin: Origin, event: &Rc<dyn Event>) -> Option<SendGuard> {
+    let key = (origin, Rc::as_ptr(event) as *const ());
+    SENDING.with(|s| {
+        let mut sending = s.borrow_mut();
+        if sending.contains(&key) {
+            trace!(target: "easy_signals::dispatch", "{:?} looped back to a subject already sending it", EventName::of(&**event));
+            None
+        } else {
+            sending.push(key);
+            Some(SendGuard { key })
+        }
+    })
+}
+
+/// Called when a dispatch finishes without notifying any observer.
+pub(crate) fn report_unhandled(event: &Rc<dyn Event>) {
+    trace!(target: "easy_signals::dispatch", "{:?} reached no observers", EventName::of(&**event));
+    let handler = UNHANDLED.with(|u| u.borrow().clone());
+    if let Some(handler) = handler {
+        handler(event);
+    }
+}
+
+fn intercept(event: &Rc<dyn Event>) {
+    let interceptor = INTERCEPTOR.with(|i| i.borrow().clone());
+    if let Some(interceptor) = interceptor {
+        interceptor(event);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::any::Any;
+    use std::cell::Cell;
+    use easy_events::implement_event;
+    use std::panic::{self, AssertUnwindSafe};
+    use crate::{ClosureObserver, ForwardingObserver, SignalObserver, SignalQueue, SignalSubject, SignalSubjectExt, SignalSnapShot, implement_signal_subject};
+
+    #[derive(Default)]
+    struct Subject {
+        subs: Vec<Rc<RefCell<dyn SignalObserver>>>
+    }
+
+    implement_signal_subject!(Subject, subs);
+
+    struct EventA;
+    implement_event!(EventA, "event_a");
+
+    #[test]
+    fn interceptor_test() {
+        let seen = Rc::new(Cell::new(0));
+        let counter = seen.clone();
+        set_interceptor(move |event| {
+            assert!(event.as_any().is::<EventA>());
+            counter.set(counter.get() + 1);
+        });
+
+        let subject = Subject::default();
+        subject.send_signal(Rc::new(EventA{}));
+        subject.send_signal_to(Rc::new(EventA{}), &Vec::<Rc<RefCell<dyn SignalObserver>>>::new());
+        SignalSnapShot::new(Rc::new(EventA{}), Vec::new()).execute();
+        assert_eq!(seen.get(), 3);
+
+        clear_interceptor();
+        subject.send_signal(Rc::new(EventA{}));
+        assert_eq!(seen.get(), 3);
+    }
+
+    #[test]
+    fn max_dispatch_depth_test() {
+        // Each subject forwards to the next through its own observer, so every
+        // level is a fresh dispatch and nothing is borrowed twice.
+        let chain: Vec<Rc<RefCell<Subject>>> = (0..12).map(|_| Rc::new(RefCell::new(Subject::default()))).collect();
+        for pair in chain.windows(2) {
+            pair[0].borrow_mut().subscribe_observer(ForwardingObserver::new(pair[1].clone()));
+        }
+
+        set_max_dispatch_depth(16);
+        chain[0].borrow().send_signal(Rc::new(EventA{}));
+
+        set_max_dispatch_depth(8);
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            chain[0].borrow().send_signal(Rc::new(EventA{}));
+        }));
+        let payload = result.unwrap_err();
+        assert_eq!(payload.downcast_ref::<String>().map(String::as_str), Some("signal dispatch exceeded the maximum depth of 8"));
+        assert_eq!(dispatch_depth(), 0);
+        assert!(SENDING.with(|s| s.borrow().is_empty()));
+
+        clear_max_dispatch_depth();
+    }
+
+    #[test]
+    fn unhandled_handler_test() {
+        let unhandled = Rc::new(Cell::new(0));
+        let counter = unhandled.clone();
+        set_unhandled_handler(move |event| {
+            assert!(event.as_any().is::<EventA>());
+            counter.set(counter.get() + 1);
+        });
+
+        let mut subject = Subject::default();
+        subject.send_signal(Rc::new(EventA{}));
+        SignalSnapShot::new(Rc::new(EventA{}), Vec::new()).execute();
+        assert!(subject.try_send_signal(Rc::new(EventA{})).is_empty());
+        assert_eq!(unhandled.get(), 3);
+
+        subject.subscribe_observer(ClosureObserver::new(|_event| {}));
+        subject.send_signal(Rc::new(EventA{}));
+        assert_eq!(unhandled.get(), 3);
+
+        clear_unhandled_handler();
+        subject.clear_observers();
+        subject.send_signal(Rc::new(EventA{}));
+        assert_eq!(unhandled.get(), 3);
+    }
+
+    #[test]
+    fn forwarding_cycle_test() {
+        let a = Rc::new(RefCell::new(Subject::default()));
+        let b = Rc::new(RefCell::new(Subject::default()));
+        let received = Rc::new(Cell::new(0));
+        let counter = received.clone();
+        b.borrow_mut().subscribe_observer(ClosureObserver::new(move |_event| {
+            counter.set(counter.get() + 1);
+        }));
+        a.borrow_mut().subscribe_observer(ForwardingObserver::new(b.clone()));
+        b.borrow_mut().subscribe_observer(ForwardingObserver::new(a.clone()));
+
+        a.borrow().send_signal(Rc::new(EventA{}));
+        assert_eq!(received.get(), 1);
+        assert!(SENDING.with(|s| s.borrow().is_empty()));
+
+        // A new event is a new signal, so it is forwarded again.
+        a.borrow().send_signal(Rc::new(EventA{}));
+        assert_eq!(received.get(), 2);
+
+        a.borrow_mut().clear_observers();
+        b.borrow_mut().clear_observers();
+    }
+
+    fn emit_cycle(received: &Rc<Cell<u32>>) -> (Rc<RefCell<Subject>>, Rc<RefCell<Subject>>) {
+        let a = Rc::new(RefCell::new(Subject::default()));
+        let b = Rc::new(RefCell::new(Subject::default()));
+        let counter = received.clone();
+        let to_a = Rc::downgrade(&a);
+        b.borrow_mut().subscribe_observer(ClosureObserver::new(move |event| {
+            counter.set(counter.get() + 1);
+            to_a.upgrade().unwrap().emit(event);
+        }));
+        let to_b = Rc::downgrade(&b);
+        a.borrow_mut().subscribe_observer(ClosureObserver::new(move |event| {
+            to_b.upgrade().unwrap().emit(event);
+        }));
+        (a, b)
+    }
+
+    #[test]
+    fn emit_cycle_test() {
+        let received = Rc::new(Cell::new(0));
+        let (a, _b) = emit_cycle(&received);
+
+        assert_eq!(a.emit(Rc::new(EventA{})), 1);
+        assert_eq!(received.get(), 1);
+        assert!(SENDING.with(|s| s.borrow().is_empty()));
+    }
+
+    #[test]
+    fn queued_snapshot_cycle_test() {
+        let received = Rc::new(Cell::new(0));
+        let (a, _b) = emit_cycle(&received);
+        let queue = SignalQueue::new();
+        queue.push(a.borrow().get_signal_snapshot(Rc::new(EventA{})));
+        let weak = SignalQueue::new();
+        weak.push(a.borrow().get_weak_signal_snapshot(Rc::new(EventA{})));
+        let lazy = SignalQueue::new();
+        lazy.push(a.get_lazy_signal_snapshot(Rc::new(EventA{})));
+
+        assert_eq!(queue.process_all(), 1);
+        assert_eq!(weak.process_all(), 1);
+        assert_eq!(lazy.process_all(), 1);
+        assert_eq!(received.get(), 3);
+        assert!(SENDING.with(|s| s.borrow().is_empty()));
+    }
+
+    #[test]
+    fn event_name_test() {
+        struct Named;
+        implement_event!(Named, "named");
+        struct Unnamed;
+        implement_event!(Unnamed, "unnamed");
+
+        register_event_name::<Named>();
+        assert_eq!(event_name(&Named), Some(std::any::type_name::<Named>()));
+        assert_eq!(event_name(&Unnamed), None);
+        assert_eq!(format!("{:?}", EventName::of(&Unnamed)), format!("{:?}", TypeId::of::<Unnamed>()));
+
+        struct Elsewhere;
+        implement_event!(Elsewhere, "elsewhere");
+        std::thread::spawn(register_event_name::<Elsewhere>).join().unwrap();
+        assert_eq!(event_name(&Elsewhere), Some(std::any::type_name::<Elsewhere>()));
+    }
+}
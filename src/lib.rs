@@ -1,256 +1,3362 @@
-use std::rc::Rc;
-use std::cell::RefCell;
+#![cfg_attr(all(not(feature = "std"), not(test)), no_std)]
+
+extern crate alloc;
+
+use alloc::rc::{Rc, Weak};
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use alloc::collections::VecDeque;
+use core::cell::{Cell, Ref, RefCell};
 use core::slice::Iter;
-use std::collections::VecDeque;
+use core::ops::ControlFlow;
+use core::fmt;
+use core::any::TypeId;
 use easy_events::Event;
 
-pub trait SignalObserver{
-    fn process_signal(&mut self, event: Rc<dyn Event>);
-}
+/// Emits a `log::trace!` record when the `log` feature is enabled. Otherwise
+/// the arguments are only type-checked, never evaluated.
+macro_rules! trace {
+    (target: $target:expr, $($arg:tt)+) => {
+        #[cfg(feature = "log")]
+        log::trace!(target: $target, $($arg)+);
+        #[cfg(not(feature = "log"))]
+        if false {
+            let _ = format_args!($($arg)+);
+        }
+    };
+}
+
+pub mod asynchronous;
+#[cfg(feature = "std")]
+pub mod hooks;
+pub mod prelude;
+#[cfg(feature = "std")]
+pub mod sync;
+#[cfg(all(any(test, feature = "testing"), feature = "std"))]
+pub mod testing;
+
+/// Without `std` there are no thread-locals to keep hooks in, so dispatches
+/// run none.
+#[cfg(not(feature = "std"))]
+mod hooks {
+    use alloc::rc::Rc;
+    use easy_events::Event;
+
+    pub(crate) struct DispatchGuard;
+
+    pub(crate) fn begin_dispatch(event: &Rc<dyn Event>, observers: usize) -> DispatchGuard {
+        trace!(target: "easy_signals::dispatch", "dispatching {:?} to {} observers", crate::EventName::of(&**event), observers);
+        DispatchGuard
+    }
+
+    pub(crate) fn report_unhandled(_event: &Rc<dyn Event>) {}
+
+    pub(crate) struct SendGuard;
+
+    pub(crate) fn begin_send(_origin: crate::Origin, _event: &Rc<dyn Event>) -> Option<SendGuard> {
+        Some(SendGuard)
+    }
+
+    pub(crate) fn registered_name(_id: core::any::TypeId) -> Option<&'static str> {
+        None
+    }
+}
+
+mod bus;
+mod emit;
+mod observers;
+mod priority_queue;
+mod router;
+mod scheduled;
+mod typed;
+mod typed_subject;
+pub use bus::EventBus;
+pub use emit::SignalEmitter;
+#[cfg(feature = "std")]
+pub use observers::ChannelObserver;
+pub use observers::{ClosureObserver, CountedObserver, EventTypeSet, FilteredObserver, ForwardingObserver, GateHandle, GatedObserver, MultiplexObserver, OnceObserver, PrioritizedObserver, TaggedObserver, WeakObserver};
+pub use priority_queue::SignalPriorityQueue;
+pub use router::SignalRouter;
+pub use scheduled::{RepeatHandle, ScheduledSignalQueue};
+pub use typed::{TypedObserver, TypedObserverAdapter};
+pub use typed_subject::{TypedSignalObserver, TypedSubject};
+
+#[cfg(feature = "derive")]
+pub use easy_signals_derive::SignalSubject;
+
+pub type SignalError = Box<dyn core::error::Error>;
+
+type ObserverPredicate<'a> = dyn FnMut(&Rc<RefCell<dyn SignalObserver>>) -> bool + 'a;
+
+/// Converts an observer `Rc` to the `dyn SignalObserver` form subjects store,
+/// for both concrete observer types and ones that are already `dyn`.
+pub trait AsSignalObserver {
+    fn as_signal_observer(&self) -> Rc<RefCell<dyn SignalObserver>>;
+}
+
+impl<O: SignalObserver + 'static> AsSignalObserver for Rc<RefCell<O>> {
+    fn as_signal_observer(&self) -> Rc<RefCell<dyn SignalObserver>> {
+        self.clone()
+    }
+}
+
+impl AsSignalObserver for Rc<RefCell<dyn SignalObserver>> {
+    fn as_signal_observer(&self) -> Rc<RefCell<dyn SignalObserver>> {
+        self.clone()
+    }
+}
+
+/// The extra observers taken by `SignalSubject::send_signal_to` and
+/// `get_signal_to_snapshot`: a `Vec`, an array, or a reference to a slice
+/// of anything `AsSignalObserver`. Being a trait object parameter lets those
+/// methods stay callable on a `dyn SignalSubject`.
+pub trait SignalTargets {
+    fn target_observers(&self) -> Vec<Rc<RefCell<dyn SignalObserver>>>;
+}
+
+impl<T: AsSignalObserver> SignalTargets for Vec<T> {
+    fn target_observers(&self) -> Vec<Rc<RefCell<dyn SignalObserver>>> {
+        self.iter().map(AsSignalObserver::as_signal_observer).collect()
+    }
+}
+
+impl<T: AsSignalObserver, const N: usize> SignalTargets for [T; N] {
+    fn target_observers(&self) -> Vec<Rc<RefCell<dyn SignalObserver>>> {
+        self.iter().map(AsSignalObserver::as_signal_observer).collect()
+    }
+}
+
+impl<T: AsSignalObserver> SignalTargets for &[T] {
+    fn target_observers(&self) -> Vec<Rc<RefCell<dyn SignalObserver>>> {
+        self.iter().map(AsSignalObserver::as_signal_observer).collect()
+    }
+}
+
+/// The list a subject keeps its observers in, as returned by
+/// `SignalSubject::get_observers_mut`. A subject picks its storage by the type
+/// of its observers field: a `Vec<Rc<RefCell<dyn SignalObserver>>>`, or with the
+/// `smallvec` feature a `SmallVec` such as `SmallObserverList`, which holds a
+/// few observers without allocating.
+pub trait ObserverStorage {
+    fn as_slice(&self) -> &[Rc<RefCell<dyn SignalObserver>>];
+    fn as_mut_slice(&mut self) -> &mut [Rc<RefCell<dyn SignalObserver>>];
+    fn insert(&mut self, index: usize, observer: Rc<RefCell<dyn SignalObserver>>);
+    fn remove(&mut self, index: usize) -> Rc<RefCell<dyn SignalObserver>>;
+    /// Keeps only the observers for which `f` returns true.
+    fn retain(&mut self, f: &mut ObserverPredicate<'_>);
+    fn reserve(&mut self, additional: usize);
+    fn shrink_to_fit(&mut self);
+    fn clear(&mut self);
+    /// Empties the list, returning its observers in order.
+    fn take(&mut self) -> Vec<Rc<RefCell<dyn SignalObserver>>>;
+    fn len(&self) -> usize {
+        self.as_slice().len()
+    }
+    fn is_empty(&self) -> bool {
+        self.as_slice().is_empty()
+    }
+}
+
+impl ObserverStorage for Vec<Rc<RefCell<dyn SignalObserver>>> {
+    fn as_slice(&self) -> &[Rc<RefCell<dyn SignalObserver>>] {
+        self
+    }
+
+    fn as_mut_slice(&mut self) -> &mut [Rc<RefCell<dyn SignalObserver>>] {
+        self
+    }
+
+    fn insert(&mut self, index: usize, observer: Rc<RefCell<dyn SignalObserver>>) {
+        Vec::insert(self, index, observer);
+    }
+
+    fn remove(&mut self, index: usize) -> Rc<RefCell<dyn SignalObserver>> {
+        Vec::remove(self, index)
+    }
+
+    fn retain(&mut self, f: &mut ObserverPredicate<'_>) {
+        Vec::retain(self, f);
+    }
+
+    fn reserve(&mut self, additional: usize) {
+        Vec::reserve(self, additional);
+    }
+
+    fn shrink_to_fit(&mut self) {
+        Vec::shrink_to_fit(self);
+    }
+
+    fn clear(&mut self) {
+        Vec::clear(self);
+    }
+
+    fn take(&mut self) -> Vec<Rc<RefCell<dyn SignalObserver>>> {
+        core::mem::take(self)
+    }
+}
+
+/// Observer storage for subjects that almost always have at most four
+/// observers, keeping them inline rather than on the heap.
+#[cfg(feature = "smallvec")]
+pub type SmallObserverList = smallvec::SmallVec<[Rc<RefCell<dyn SignalObserver>>; 4]>;
+
+#[cfg(feature = "smallvec")]
+impl<A: smallvec::Array<Item = Rc<RefCell<dyn SignalObserver>>>> ObserverStorage for smallvec::SmallVec<A> {
+    fn as_slice(&self) -> &[Rc<RefCell<dyn SignalObserver>>] {
+        self
+    }
+
+    fn as_mut_slice(&mut self) -> &mut [Rc<RefCell<dyn SignalObserver>>] {
+        self
+    }
+
+    fn insert(&mut self, index: usize, observer: Rc<RefCell<dyn SignalObserver>>) {
+        smallvec::SmallVec::insert(self, index, observer);
+    }
+
+    fn remove(&mut self, index: usize) -> Rc<RefCell<dyn SignalObserver>> {
+        smallvec::SmallVec::remove(self, index)
+    }
+
+    fn retain(&mut self, f: &mut ObserverPredicate<'_>) {
+        smallvec::SmallVec::retain(self, |x| f(x));
+    }
+
+    fn reserve(&mut self, additional: usize) {
+        smallvec::SmallVec::reserve(self, additional);
+    }
+
+    fn shrink_to_fit(&mut self) {
+        smallvec::SmallVec::shrink_to_fit(self);
+    }
+
+    fn clear(&mut self) {
+        smallvec::SmallVec::clear(self);
+    }
+
+    fn take(&mut self) -> Vec<Rc<RefCell<dyn SignalObserver>>> {
+        core::mem::take(self).into_vec()
+    }
+}
+
+/// An observer that panicked during `send_signal_catch_unwind`, together with
+/// the panic payload.
+#[cfg(feature = "std")]
+pub type ObserverPanic = (Rc<RefCell<dyn SignalObserver>>, Box<dyn core::any::Any + Send>);
+
+#[doc(hidden)]
+pub mod __private {
+    pub use alloc::rc::Rc;
+    pub use alloc::vec::Vec;
+    pub use easy_events::Event;
+}
+
+pub trait SignalObserver{
+    fn process_signal(&mut self, event: Rc<dyn Event>);
+    /// Fallible counterpart of `process_signal`, used by
+    /// `SignalSubject::try_send_signal`. Observers that can fail override this
+    /// and usually implement `process_signal` by discarding its error.
+    fn try_process_signal(&mut self, event: Rc<dyn Event>) -> Result<(), SignalError> {
+        self.process_signal(event);
+        Ok(())
+    }
+    /// Used by `SignalSubject::send_signal_interruptible`. Returning `Break`
+    /// stops the signal from reaching observers later in subscription order.
+    fn process_signal_interruptible(&mut self, event: Rc<dyn Event>) -> ControlFlow<()> {
+        self.process_signal(event);
+        ControlFlow::Continue(())
+    }
+    /// Called after the observer is subscribed through
+    /// `SignalSubjectExt::attach_observer`, with the subject no longer
+    /// borrowed.
+    ///
+    /// This needs the `Rc` the subject lives in, which only
+    /// `SignalSubjectExt` has. The `subscribe_*` methods of `SignalSubject`
+    /// only get `&mut self`, and `SignalRouter::subscribe` keeps its lists
+    /// outside any `Rc`, so none of them can call it. Adapters forward the
+    /// call, so to subscribe e.g. a once-only observer with it, attach
+    /// `OnceObserver::new(observer)` rather than using `subscribe_once`.
+    fn on_subscribe(&mut self, _subject: &Rc<RefCell<dyn SignalSubject>>) {}
+    /// Called whenever a subject removes the observer, whether by
+    /// unsubscribing, replacing, clearing, draining, retaining, removing its
+    /// group or pruning it once expired, while the subject is still mutably
+    /// borrowed. It is skipped for an observer that is mid-dispatch and
+    /// cannot be borrowed.
+    fn on_unsubscribe(&mut self) {}
+    /// Used by `SignalSubject::send_signal_with_source`, which also passes the
+    /// subject the signal came from. Ignores the source by default.
+    fn process_signal_from(&mut self, event: Rc<dyn Event>, _source: Rc<RefCell<dyn SignalSubject>>) {
+        self.process_signal(event);
+    }
+    /// Observers with a higher priority are placed ahead of lower ones when
+    /// subscribed, and so are notified first.
+    fn priority(&self) -> i32 {
+        0
+    }
+    /// Adapters that forward to another observer return it here, so that a
+    /// subject treats the adapter and the observer it wraps as the same
+    /// subscription.
+    fn wrapped_observer(&self) -> Option<Rc<RefCell<dyn SignalObserver>>> {
+        None
+    }
+    /// The group an observer belongs to, as set by `TaggedObserver`. A
+    /// subject checks every observer in a chain of adapters for a tag.
+    fn tag(&self) -> Option<&str> {
+        None
+    }
+    /// Expired observers are skipped during dispatch and removed from a
+    /// subject the next time it subscribes an observer or
+    /// `prune_dead_observers` is called.
+    ///
+    /// This is also how an observer detaches itself: it cannot change the
+    /// subject's list while being dispatched to, but it can set a flag in
+    /// `process_signal` that makes this return true. The signal in flight is
+    /// unaffected, the observer receives nothing further, and the subject
+    /// drops it at the next prune, e.g. straight after the dispatch with
+    /// `SignalSubjectExt::send_signal_and_prune`.
+    fn is_expired(&self) -> bool {
+        false
+    }
+    /// Events this returns false for are not delivered to the observer.
+    fn accepts(&self, _event: &dyn Event) -> bool {
+        true
+    }
+}
+
+/// Something observers can subscribe to and that sends signals to them.
+///
+/// # Ordering
+///
+/// Observers are notified in descending `SignalObserver::priority` order and,
+/// within a priority, in the order they were subscribed. Subscribing an
+/// observer that is already subscribed keeps its position, unless that changes
+/// its priority, in which case it moves to the back of its new priority.
+/// `send_signal_reversed` notifies in exactly the opposite order.
+///
+/// # Reentrancy
+///
+/// Notifying an observer mutably borrows its `RefCell`, so dispatch panics if
+/// an observer is already borrowed. This happens when an object that is both a
+/// subject and one of its own observers sends a signal while borrowed, or
+/// when an observer ends up notified again while its `process_signal` is
+/// still running. Dispatching through `get_signal_snapshot(event).execute()`
+/// avoids holding the subject's own borrow during dispatch, and
+/// `try_send_signal` reports observers that cannot be borrowed as errors
+/// instead of panicking.
+pub trait SignalSubject {
+    fn get_observers_iter(&self) -> Iter<Rc<RefCell<dyn SignalObserver>>>;
+    fn get_observers_mut(&mut self) -> &mut dyn ObserverStorage;
+    /// Subscribes an observer, replacing any existing subscription of the same
+    /// observer in place. Observers are kept in descending `priority` order,
+    /// with equal priorities notified in the order they were first subscribed.
+    ///
+    /// Priorities are read by borrowing the subscribed observers. An observer
+    /// that is mid-dispatch cannot be borrowed and is placed as if it had the
+    /// default priority of 0.
+    ///
+    /// The observer's `on_subscribe` is not called; see
+    /// `SignalObserver::on_subscribe` for why.
+    fn subscribe_observer(&mut self, new_observer: Rc<RefCell<dyn SignalObserver>>) {
+        let priority = observer_priority(&new_observer);
+        let observers = self.get_observers_mut();
+        remove_observers(observers, &mut |x| observer_expired(x));
+        if let Some(index) = observers.as_slice().iter().position(|x| same_observer(x, &new_observer)) {
+            if observer_priority(&observers.as_slice()[index]) == priority {
+                observers.as_mut_slice()[index] = new_observer;
+                return;
+            }
+            observers.remove(index);
+        }
+        let index = observers.as_slice().iter()
+            .position(|x| observer_priority(x) < priority)
+            .unwrap_or(observers.len());
+        observers.insert(index, new_observer);
+    }
+    /// Like `subscribe_observer`, but adds a new subscription even if the
+    /// observer is already subscribed, so it is notified once per
+    /// subscription. Each `unsubscribe_observer` call removes one of them, the
+    /// first in dispatch order, and `is_subscribed` stays true until the last
+    /// is gone.
+    fn subscribe_observer_allow_duplicates(&mut self, observer: Rc<RefCell<dyn SignalObserver>>) {
+        let priority = observer_priority(&observer);
+        let observers = self.get_observers_mut();
+        remove_observers(observers, &mut |x| observer_expired(x));
+        let index = observers.as_slice().iter()
+            .position(|x| observer_priority(x) < priority)
+            .unwrap_or(observers.len());
+        observers.insert(index, observer);
+    }
+    /// Subscribes each observer in turn, as `subscribe_observer` would.
+    fn subscribe_many<I: IntoIterator<Item = Rc<RefCell<dyn SignalObserver>>>>(&mut self, observers: I)
+    where
+        Self: Sized
+    {
+        let observers = observers.into_iter();
+        self.get_observers_mut().reserve(observers.size_hint().0);
+        for observer in observers {
+            self.subscribe_observer(observer);
+        }
+    }
+    fn subscribe_observer_with_priority(&mut self, observer: Rc<RefCell<dyn SignalObserver>>, priority: i32) {
+        self.subscribe_observer(PrioritizedObserver::new(observer, priority));
+    }
+    /// Subscribes an observer without keeping it alive. Once every strong
+    /// reference to it is dropped it stops receiving signals and is pruned.
+    fn subscribe_weak_observer(&mut self, observer: Weak<RefCell<dyn SignalObserver>>) {
+        self.subscribe_observer(WeakObserver::new(observer));
+    }
+    /// Subscribes an observer that only receives the next signal, after which
+    /// it is skipped and pruned like any other expired observer.
+    fn subscribe_once(&mut self, observer: Rc<RefCell<dyn SignalObserver>>) {
+        self.subscribe_observer(OnceObserver::new(observer));
+    }
+    /// Subscribes an observer that only receives the next `count` signals,
+    /// after which it is skipped and pruned like any other expired observer.
+    fn subscribe_for_count(&mut self, observer: Rc<RefCell<dyn SignalObserver>>, count: usize) {
+        self.subscribe_observer(CountedObserver::new(observer, count));
+    }
+    /// Subscribes an observer as a member of the group `tag`.
+    fn subscribe_observer_tagged(&mut self, observer: Rc<RefCell<dyn SignalObserver>>, tag: &str) {
+        self.subscribe_observer(TaggedObserver::new(observer, tag));
+    }
+    /// Removes every observer in the group `tag`, returning how many were
+    /// removed.
+    fn unsubscribe_group(&mut self, tag: &str) -> usize {
+        remove_observers(self.get_observers_mut(), &mut |x| observer_has_tag(x, tag))
+    }
+    /// Subscribes an observer behind a gate, returning the handle that
+    /// disables and re-enables it without unsubscribing it.
+    fn subscribe_gated(&mut self, observer: Rc<RefCell<dyn SignalObserver>>) -> GateHandle {
+        let (gated, handle) = GatedObserver::new(observer);
+        self.subscribe_observer(gated);
+        handle
+    }
+    /// Subscribes an observer that is only sent events whose concrete type is
+    /// one of `event_types`.
+    fn subscribe_observer_filtered(&mut self, observer: Rc<RefCell<dyn SignalObserver>>, event_types: &[TypeId]) {
+        let event_types: EventTypeSet = event_types.iter().copied().collect();
+        self.subscribe_observer(FilteredObserver::new(observer, event_types));
+    }
+    fn subscribe_observer_for<E: Event + 'static>(&mut self, observer: Rc<RefCell<dyn SignalObserver>>)
+    where
+        Self: Sized
+    {
+        self.subscribe_observer_filtered(observer, &[TypeId::of::<E>()]);
+    }
+    /// Subscribes a closure without having to wrap it in a `ClosureObserver`
+    /// first. The returned handle can be passed to `unsubscribe_observer`.
+    fn subscribe_fn(&mut self, f: Box<dyn FnMut(Rc<dyn Event>)>) -> Rc<RefCell<dyn SignalObserver>> {
+        let observer: Rc<RefCell<dyn SignalObserver>> = ClosureObserver::new(f);
+        self.subscribe_observer(observer.clone());
+        observer
+    }
+    /// Removes every expired observer, returning how many were removed.
+    fn prune_dead_observers(&mut self) -> usize {
+        remove_observers(self.get_observers_mut(), &mut |x| observer_expired(x))
+    }
+    /// Removes an observer and calls its `on_unsubscribe`, unless it is
+    /// mid-dispatch and cannot be borrowed.
+    fn unsubscribe_observer(&mut self, observer: &Rc<RefCell<dyn SignalObserver>>) -> bool {
+        let observers = self.get_observers_mut();
+        if let Some(index) = observers.as_slice().iter().position(|x| same_observer(x, observer)) {
+            notify_unsubscribed(&observers.remove(index));
+            true
+        } else {
+            false
+        }
+    }
+    /// Puts `new` in the place of `old` in the dispatch order, returning
+    /// whether `old` was subscribed. The position is kept even if the two
+    /// observers have different priorities. If `new` was already subscribed
+    /// elsewhere, that subscription is removed. `old` has its
+    /// `on_unsubscribe` called unless it is the same observer as `new`.
+    fn replace_observer(&mut self, old: &Rc<RefCell<dyn SignalObserver>>, new: Rc<RefCell<dyn SignalObserver>>) -> bool {
+        let observers = self.get_observers_mut();
+        if let Some(mut index) = observers.as_slice().iter().position(|x| same_observer(x, old)) {
+            if let Some(existing) = observers.as_slice().iter().position(|x| same_observer(x, &new)) {
+                if existing != index {
+                    observers.remove(existing);
+                    if existing < index {
+                        index -= 1;
+                    }
+                }
+            }
+            let old = core::mem::replace(&mut observers.as_mut_slice()[index], new);
+            if !same_observer(&old, &observers.as_slice()[index]) {
+                notify_unsubscribed(&old);
+            }
+            true
+        } else {
+            false
+        }
+    }
+    /// Subscribes an observer at `index` in the dispatch order, ignoring
+    /// priorities. An index past the end appends the observer. If it was
+    /// already subscribed, it is moved: the index then counts positions with
+    /// the old subscription removed.
+    fn insert_observer_at(&mut self, index: usize, observer: Rc<RefCell<dyn SignalObserver>>) {
+        let observers = self.get_observers_mut();
+        observers.retain(&mut |x| !same_observer(x, &observer));
+        let index = index.min(observers.len());
+        observers.insert(index, observer);
+    }
+    /// Subscribes an observer directly after `anchor` in the dispatch order,
+    /// moving it if it was already subscribed. Returns false, leaving the
+    /// subject unchanged, if `anchor` is not subscribed.
+    fn insert_observer_after(&mut self, anchor: &Rc<RefCell<dyn SignalObserver>>, observer: Rc<RefCell<dyn SignalObserver>>) -> bool {
+        if same_observer(anchor, &observer) {
+            return self.is_subscribed(anchor);
+        }
+        let observers = self.get_observers_mut();
+        if !observers.as_slice().iter().any(|x| same_observer(x, anchor)) {
+            return false;
+        }
+        observers.retain(&mut |x| !same_observer(x, &observer));
+        let index = observers.as_slice().iter().position(|x| same_observer(x, anchor)).unwrap_or(observers.len());
+        observers.insert(index + 1, observer);
+        true
+    }
+    /// Keeps only the observers for which `f` returns true.
+    fn retain_observers<F: FnMut(&Rc<RefCell<dyn SignalObserver>>) -> bool>(&mut self, mut f: F)
+    where
+        Self: Sized
+    {
+        remove_observers(self.get_observers_mut(), &mut |x| !f(x));
+    }
+    /// Reserves room for at least `additional` more observers, to avoid
+    /// reallocating while subscribing. This is best-effort: it is only a hint
+    /// to the backing `ObserverStorage`, and unsubscribing or pruning still shifts
+    /// observers within it.
+    fn reserve_observers(&mut self, additional: usize) {
+        self.get_observers_mut().reserve(additional);
+    }
+    /// Releases any capacity the observer list is not currently using.
+    fn shrink_observers_to_fit(&mut self) {
+        self.get_observers_mut().shrink_to_fit();
+    }
+    fn clear_observers(&mut self) -> usize {
+        self.drain_observers().len()
+    }
+    /// Removes every observer and returns them in dispatch order, e.g. to
+    /// move them to another subject with `subscribe_many`.
+    fn drain_observers(&mut self) -> Vec<Rc<RefCell<dyn SignalObserver>>> {
+        let drained = self.get_observers_mut().take();
+        for observer in drained.iter() {
+            notify_unsubscribed(observer);
+        }
+        drained
+    }
+    fn observer_count(&self) -> usize {
+        self.get_observers_iter().len()
+    }
+    fn has_observers(&self) -> bool {
+        self.observer_count() > 0
+    }
+    /// Identity is by `Rc` pointer, not by value: two distinct observers that
+    /// compare equal are still different subscriptions. An adapter such as
+    /// `PrioritizedObserver` counts as the observer it wraps.
+    fn is_subscribed(&self, observer: &Rc<RefCell<dyn SignalObserver>>) -> bool {
+        self.get_observers_iter().any(|x| same_observer(x, observer))
+    }
+    /// Copies the observer list in dispatch order, as described under
+    /// Ordering. Observers subscribed only through `subscribe_observer` and
+    /// the methods built on it never appear twice, but entries pushed onto the
+    /// backing `ObserverStorage` directly are copied as they are; see `unique_observers`.
+    fn copy_observers(&self) -> Vec<Rc<RefCell<dyn SignalObserver>>> {
+        let mut copy = Vec::new();
+        for o in self.get_observers_iter() {
+            copy.push(o.clone());
+        }
+        copy
+    }
+    /// Like `copy_observers`, but keeps only the first entry for each
+    /// observer, compared as in `is_subscribed`.
+    fn unique_observers(&self) -> Vec<Rc<RefCell<dyn SignalObserver>>> {
+        let mut unique = Vec::new();
+        append_unique_observers(&mut unique, self.get_observers_iter().as_slice());
+        unique
+    }
+    /// Dispatches over a copy of the observer list, so observers subscribed or
+    /// unsubscribed during dispatch only take part in the next signal.
+    ///
+    /// An observer can only reach back into a subject held in a `RefCell` once
+    /// the caller's borrow is released. `SignalSubjectExt::emit` does this: it
+    /// borrows the subject only to copy its observers, then dispatches.
+    ///
+    /// If an observer leads back to this subject sending the same event
+    /// again, for example through a `ForwardingObserver` loop, the inner call
+    /// notifies no one and returns 0, so the cycle stops there. Sending a new
+    /// event, or the same event to a different subject, is unaffected. The
+    /// same check covers every `send_signal_*` method, `signal`, and snapshots
+    /// taken from this subject, which count as sent by it. Without `std` this
+    /// check is not made.
+    fn send_signal(&self, event: Rc<dyn Event>) -> usize {
+        dispatch_signal(Origin::of(self), &self.copy_observers(), event)
+    }
+    /// Sends an event that is already boxed. Dispatch still needs an `Rc`, so
+    /// the event is moved into a new `Rc` allocation first.
+    fn send_signal_boxed(&self, event: Box<dyn Event>) -> usize {
+        self.send_signal(Rc::from(event))
+    }
+    /// Sends each event in turn to the same copy of the observer list: every
+    /// observer receives `events[0]`, then every observer receives
+    /// `events[1]`, and so on. Returns the total number of notifications.
+    fn send_signals(&self, events: &[Rc<dyn Event>]) -> usize {
+        let observers = self.copy_observers();
+        events.iter().map(|event| dispatch_signal(Origin::of(self), &observers, event.clone())).sum()
+    }
+    /// Dispatches only to the observers in the group `tag`.
+    fn send_signal_to_group(&self, event: Rc<dyn Event>, tag: &str) -> usize {
+        let mut subs = self.copy_observers();
+        subs.retain(|x| observer_has_tag(x, tag));
+        dispatch_signal(Origin::of(self), &subs, event)
+    }
+    /// Like `send_signal`, but notifies observers last-subscribed first.
+    fn send_signal_reversed(&self, event: Rc<dyn Event>) -> usize {
+        let mut subs = self.copy_observers();
+        subs.reverse();
+        dispatch_signal(Origin::of(self), &subs, event)
+    }
+    /// Like `send_signal`, but dispatches through `try_process_signal` and
+    /// collects every error. A failing observer does not stop the remaining
+    /// observers from being notified. An observer that is already borrowed,
+    /// as described under Reentrancy, is skipped and reported as an error.
+    fn try_send_signal(&self, event: Rc<dyn Event>) -> Vec<SignalError> {
+        let mut errors = Vec::new();
+        dispatch_with(Origin::of(self), &self.copy_observers(), event, |o, event| {
+            if let Err(e) = try_process(o, event) {
+                errors.push(e);
+            }
+            ControlFlow::Continue(())
+        });
+        errors
+    }
+    /// Like `send_signal`, but catches a panic from any observer and carries
+    /// on with the remaining ones, returning the observers that panicked
+    /// together with their payloads. The panic hook still runs for each panic.
+    ///
+    /// Observers are called through `AssertUnwindSafe`, so one that panics
+    /// halfway through `process_signal` is left in whatever state it reached
+    /// and may be notified again by later dispatches. Unsubscribe observers
+    /// that panicked if their state can no longer be trusted.
+    #[cfg(feature = "std")]
+    fn send_signal_catch_unwind(&self, event: Rc<dyn Event>) -> Vec<ObserverPanic> {
+        let mut panics = Vec::new();
+        dispatch_with(Origin::of(self), &self.copy_observers(), event, |o, event| {
+            let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+                o.borrow_mut().process_signal(event);
+            }));
+            if let Err(payload) = result {
+                panics.push((o.clone(), payload));
+            }
+            ControlFlow::Continue(())
+        });
+        panics
+    }
+    /// Dispatches in subscription order through `process_signal_interruptible`
+    /// until an observer returns `Break`. Returns the number of observers
+    /// notified, including the one that stopped propagation.
+    fn send_signal_interruptible(&self, event: Rc<dyn Event>) -> usize {
+        dispatch_with(Origin::of(self), &self.copy_observers(), event, |o, event| {
+            o.borrow_mut().process_signal_interruptible(event)
+        })
+    }
+    /// Like `send_signal`, but delivers through `process_signal_from` so
+    /// observers can tell which subject the signal came from. The caller
+    /// usually still holds a borrow of `source`, so observers can at most
+    /// borrow it immutably.
+    fn send_signal_with_source(&self, event: Rc<dyn Event>, source: Rc<RefCell<dyn SignalSubject>>) -> usize {
+        dispatch_with(Origin::of(self), &self.copy_observers(), event, |o, event| {
+            o.borrow_mut().process_signal_from(event, source.clone());
+            ControlFlow::Continue(())
+        })
+    }
+    /// Captures the event together with the current observers. Observers
+    /// subscribed after this returns are not notified when it executes; see
+    /// `SignalSubjectExt::get_lazy_signal_snapshot` for the alternative.
+    fn get_signal_snapshot(&self, event: Rc<dyn Event>) -> SignalSnapShot {
+        SignalSnapShot::from_subject(Origin::of(self), event, self.copy_observers())
+    }
+    /// Like `get_signal_snapshot`, but the snapshot holds the observers
+    /// weakly and skips those dropped before it executes.
+    fn get_weak_signal_snapshot(&self, event: Rc<dyn Event>) -> WeakSignalSnapShot {
+        self.get_signal_snapshot(event).downgrade()
+    }
+    /// Dispatches to the subject's observers, in their usual order, and then
+    /// to `targets` in the order given. Targets that are already subscribed
+    /// are only notified once, in their subscribed position. See
+    /// `send_signal` for how subscription changes during dispatch are
+    /// handled, and `send_signal_to_first` for the opposite order.
+    ///
+    /// `targets` is any `SignalTargets`, so a `&Vec` or array of concrete
+    /// observer `Rc`s, such as `&vec![observer.clone()]`, is accepted
+    /// without first converting each one to `dyn SignalObserver`.
+    fn send_signal_to(&self, event: Rc<dyn Event>, targets: &dyn SignalTargets) -> usize {
+        let mut subs = self.copy_observers();
+        append_unique_observers(&mut subs, &targets.target_observers());
+        dispatch_signal(Origin::of(self), &subs, event)
+    }
+    /// Like `send_signal_to`, but notifies `targets` before the subject's
+    /// observers. Subscribed targets are notified once, in their target
+    /// position.
+    fn send_signal_to_first(&self, event: Rc<dyn Event>, targets: &[Rc<RefCell<dyn SignalObserver>>]) -> usize {
+        let mut subs = Vec::new();
+        append_unique_observers(&mut subs, targets);
+        append_unique_observers(&mut subs, &self.copy_observers());
+        dispatch_signal(Origin::of(self), &subs, event)
+    }
+    /// Dispatches to every subscribed observer except those in `excluded`.
+    fn send_signal_except(&self, event: Rc<dyn Event>, excluded: &[Rc<RefCell<dyn SignalObserver>>]) -> usize {
+        let mut subs = self.copy_observers();
+        subs.retain(|x| !excluded.iter().any(|e| same_observer(x, e)));
+        dispatch_signal(Origin::of(self), &subs, event)
+    }
+    fn get_signal_except_snapshot(&self, event: Rc<dyn Event>, excluded: &[Rc<RefCell<dyn SignalObserver>>]) -> SignalSnapShot {
+        let mut subs = self.copy_observers();
+        subs.retain(|x| !excluded.iter().any(|e| same_observer(x, e)));
+        SignalSnapShot::from_subject(Origin::of(self), event, subs)
+    }
+    /// Starts building a signal for `event` that can combine targets,
+    /// exclusions, a filter and deferral, finished by `SignalEmitter::send`.
+    /// The audience is read from the subject now. To send straight away, use
+    /// `send_signal`, or `SignalSubjectExt::emit` for a subject in an
+    /// `Rc<RefCell<..>>`.
+    ///
+    /// The builder is started by `signal` rather than `emit` because
+    /// `SignalSubjectExt::emit` already sends immediately, and
+    /// `subject.emit(event)` should not do something different from
+    /// `subject.borrow().emit(event)`.
+    fn signal<'q>(&self, event: Rc<dyn Event>) -> SignalEmitter<'q> {
+        SignalEmitter::new(Origin::of(self), event, self.copy_observers())
+    }
+    /// Dispatches only to observers for which `predicate` returns true.
+    fn send_signal_where<F: Fn(&Rc<RefCell<dyn SignalObserver>>) -> bool>(&self, event: Rc<dyn Event>, predicate: F) -> usize
+    where
+        Self: Sized
+    {
+        let mut subs = self.copy_observers();
+        subs.retain(|x| predicate(x));
+        dispatch_signal(Origin::of(self), &subs, event)
+    }
+    fn get_signal_where_snapshot<F: Fn(&Rc<RefCell<dyn SignalObserver>>) -> bool>(&self, event: Rc<dyn Event>, predicate: F) -> SignalSnapShot
+    where
+        Self: Sized
+    {
+        let mut subs = self.copy_observers();
+        subs.retain(|x| predicate(x));
+        SignalSnapShot::from_subject(Origin::of(self), event, subs)
+    }
+    fn get_signal_to_snapshot(&self, event: Rc<dyn Event>, targets: &dyn SignalTargets) -> SignalSnapShot {
+        let mut subs = self.copy_observers();
+        append_unique_observers(&mut subs, &targets.target_observers());
+        SignalSnapShot::from_subject(Origin::of(self), event, subs)
+    }
+    fn get_signal_to_first_snapshot(&self, event: Rc<dyn Event>, targets: &[Rc<RefCell<dyn SignalObserver>>]) -> SignalSnapShot {
+        let mut subs = Vec::new();
+        append_unique_observers(&mut subs, targets);
+        append_unique_observers(&mut subs, &self.copy_observers());
+        SignalSnapShot::from_subject(Origin::of(self), event, subs)
+    }
+}
+
+/// Identifies the subject a signal is sent from, for the loop check in
+/// `dispatch_with`. The address is only compared, never dereferenced.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Origin(*const ());
+
+impl Origin {
+    pub(crate) fn of<S: ?Sized>(subject: &S) -> Self {
+        Origin(subject as *const S as *const ())
+    }
+}
+
+/// Shows an event by the name registered with `hooks::register_event_name`,
+/// or by its `TypeId` if it has none.
+#[derive(Clone, Copy)]
+pub(crate) struct EventName(Option<TypeId>);
+
+impl EventName {
+    pub(crate) fn of(event: &dyn Event) -> Self {
+        EventName(Some(event.as_any().type_id()))
+    }
+
+    /// The event of a queued signal, which may not have one.
+    pub(crate) fn of_signal<S: Executable>(signal: &S) -> Self {
+        EventName(signal.event().map(|event| event.as_any().type_id()))
+    }
+}
+
+impl fmt::Debug for EventName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Some(id) = self.0 else {
+            return f.write_str("<no event>");
+        };
+        match hooks::registered_name(id) {
+            Some(name) => f.write_str(name),
+            None => fmt::Debug::fmt(&id, f)
+        }
+    }
+}
+
+fn dispatch_signal(origin: Origin, observers: &[Rc<RefCell<dyn SignalObserver>>], event: Rc<dyn Event>) -> usize {
+    dispatch_with(origin, observers, event, |o, event| {
+        o.borrow_mut().process_signal(event);
+        ControlFlow::Continue(())
+    })
+}
+
+/// Runs one dispatch, shared by every way of sending a signal: starts the
+/// dispatch hooks, calls `deliver` for each observer the event should reach
+/// until it returns `Break`, and reports the event as unhandled if it reached
+/// none. Returns how many observers `deliver` was called for.
+///
+/// A dispatch that `origin` is already running for the same event further up
+/// the stack has looped back on itself, and notifies no one.
+fn dispatch_with<F>(origin: Origin, observers: &[Rc<RefCell<dyn SignalObserver>>], event: Rc<dyn Event>, mut deliver: F) -> usize
+where
+    F: FnMut(&Rc<RefCell<dyn SignalObserver>>, Rc<dyn Event>) -> ControlFlow<()>
+{
+    let Some(_send) = hooks::begin_send(origin, &event) else {
+        return 0;
+    };
+    let _dispatch = hooks::begin_dispatch(&event, observers.len());
+    let mut count = 0;
+    for (index, o) in observers.iter().enumerate() {
+        if !should_deliver(o, &event) {
+            continue;
+        }
+        count += 1;
+        if index + 1 == observers.len() {
+            // The last observer takes the caller's reference rather than a clone.
+            let _ = deliver(o, event);
+            return count;
+        }
+        if deliver(o, event.clone()).is_break() {
+            break;
+        }
+    }
+    if count == 0 {
+        hooks::report_unhandled(&event);
+    }
+    count
+}
+
+/// Calls `try_process_signal`, reporting a borrow conflict as an error
+/// rather than panicking.
+pub(crate) fn try_process(observer: &Rc<RefCell<dyn SignalObserver>>, event: Rc<dyn Event>) -> Result<(), SignalError> {
+    observer.try_borrow_mut().map_err(Box::new)?.try_process_signal(event)
+}
+
+fn notify_unsubscribed(observer: &Rc<RefCell<dyn SignalObserver>>) {
+    if let Ok(mut observer) = observer.try_borrow_mut() {
+        observer.on_unsubscribe();
+    }
+}
+
+/// Removes the observers `remove` returns true for, calling their
+/// `on_unsubscribe`, and returns how many were removed.
+fn remove_observers(observers: &mut dyn ObserverStorage, remove: &mut ObserverPredicate<'_>) -> usize {
+    let count = observers.len();
+    observers.retain(&mut |x| {
+        let removed = remove(x);
+        if removed {
+            notify_unsubscribed(x);
+        }
+        !removed
+    });
+    count - observers.len()
+}
+
+fn observer_expired(observer: &Rc<RefCell<dyn SignalObserver>>) -> bool {
+    observer.try_borrow().is_ok_and(|o| o.is_expired())
+}
+
+fn should_deliver(observer: &Rc<RefCell<dyn SignalObserver>>, event: &Rc<dyn Event>) -> bool {
+    match observer.try_borrow() {
+        Ok(o) => !o.is_expired() && o.accepts(event.as_ref()),
+        Err(_) => true
+    }
+}
+
+fn observer_priority(observer: &Rc<RefCell<dyn SignalObserver>>) -> i32 {
+    observer.try_borrow().map_or(0, |o| o.priority())
+}
+
+fn innermost_observer(observer: &Rc<RefCell<dyn SignalObserver>>) -> Rc<RefCell<dyn SignalObserver>> {
+    let mut current = observer.clone();
+    loop {
+        let wrapped = match current.try_borrow() {
+            Ok(o) => o.wrapped_observer(),
+            Err(_) => None
+        };
+        match wrapped {
+            Some(inner) => current = inner,
+            None => return current
+        }
+    }
+}
+
+fn observer_has_tag(observer: &Rc<RefCell<dyn SignalObserver>>, tag: &str) -> bool {
+    let mut current = observer.clone();
+    loop {
+        let wrapped = match current.try_borrow() {
+            Ok(o) if o.tag() == Some(tag) => return true,
+            Ok(o) => o.wrapped_observer(),
+            Err(_) => None
+        };
+        match wrapped {
+            Some(inner) => current = inner,
+            None => return false
+        }
+    }
+}
+
+fn same_observer(a: &Rc<RefCell<dyn SignalObserver>>, b: &Rc<RefCell<dyn SignalObserver>>) -> bool {
+    Rc::ptr_eq(a, b) || Rc::ptr_eq(&innermost_observer(a), &innermost_observer(b))
+}
+
+fn append_unique_observers(subs: &mut Vec<Rc<RefCell<dyn SignalObserver>>>, targets: &[Rc<RefCell<dyn SignalObserver>>]) {
+    for t in targets.iter() {
+        if !subs.iter().any(|x| same_observer(x, t)) {
+            subs.push(t.clone());
+        }
+    }
+}
+  
+/// Implements `SignalSubject` for a struct whose `$observers_field` is an
+/// `ObserverStorage`, usually a `Vec<Rc<RefCell<dyn SignalObserver>>>`. This
+/// is all the plumbing a subject needs: every other trait method has a
+/// default built on the generated accessors. Paths are fully qualified, so no
+/// imports are required at the call site.
+#[macro_export]
+macro_rules! implement_signal_subject {
+    (
+        $struct:ident,
+        $observers_field:ident $(,)?
+    ) => {
+        impl $crate::SignalSubject for $struct {
+            fn get_observers_iter(&self) -> ::core::slice::Iter<'_, $crate::__private::Rc<::core::cell::RefCell<dyn $crate::SignalObserver>>> {
+                self.$observers_field.iter()
+            }
+
+            fn get_observers_mut(&mut self) -> &mut dyn $crate::ObserverStorage {
+                &mut self.$observers_field
+            }
+        }
+    }
+}
+
+/// Conveniences for subjects held in an `Rc<RefCell<..>>`, which borrow the
+/// subject themselves.
+pub trait SignalSubjectExt {
+    fn as_signal_subject(&self) -> Rc<RefCell<dyn SignalSubject>>;
+    /// Shorthand for `attach_observer`.
+    fn subscribe(&self, observer: Rc<RefCell<dyn SignalObserver>>) {
+        self.attach_observer(observer);
+    }
+    /// Sends `event` to the subject's observers, returning how many were
+    /// notified. The subject is only borrowed while its observers are copied,
+    /// so they are free to borrow it again during dispatch.
+    fn emit(&self, event: Rc<dyn Event>) -> usize {
+        let snapshot = self.as_signal_subject().borrow().get_signal_snapshot(event);
+        snapshot.execute()
+    }
+    /// Like `emit`, but removes expired observers once the dispatch has
+    /// finished, including any that expired while handling this signal.
+    /// Returns the number of observers notified.
+    fn send_signal_and_prune(&self, event: Rc<dyn Event>) -> usize {
+        let count = self.emit(event);
+        self.as_signal_subject().borrow_mut().prune_dead_observers();
+        count
+    }
+    /// Subscribes an observer and then calls its `on_subscribe` with this
+    /// subject, unless the observer is mid-dispatch and cannot be borrowed.
+    fn attach_observer(&self, observer: Rc<RefCell<dyn SignalObserver>>) {
+        let subject = self.as_signal_subject();
+        subject.borrow_mut().subscribe_observer(observer.clone());
+        if let Ok(mut observer) = observer.try_borrow_mut() {
+            observer.on_subscribe(&subject);
+        }
+    }
+    /// Like `attach_observer`, but unsubscribes the observer again when the
+    /// returned guard is dropped.
+    fn subscribe_observer_scoped(&self, observer: Rc<RefCell<dyn SignalObserver>>) -> SubscriptionGuard {
+        let subject = self.as_signal_subject();
+        self.attach_observer(observer.clone());
+        SubscriptionGuard {
+            subject: Rc::downgrade(&subject),
+            observer: Some(observer)
+        }
+    }
+    /// Captures the event now but leaves the audience to be read from the
+    /// subject when the snapshot executes, unlike
+    /// `SignalSubject::get_signal_snapshot`.
+    fn get_lazy_signal_snapshot(&self, event: Rc<dyn Event>) -> LazySignalSnapShot {
+        LazySignalSnapShot {
+            event,
+            subject: Rc::downgrade(&self.as_signal_subject())
+        }
+    }
+}
+
+impl<S: SignalSubject + 'static> SignalSubjectExt for Rc<RefCell<S>> {
+    fn as_signal_subject(&self) -> Rc<RefCell<dyn SignalSubject>> {
+        self.clone()
+    }
+}
+
+impl SignalSubjectExt for Rc<RefCell<dyn SignalSubject>> {
+    fn as_signal_subject(&self) -> Rc<RefCell<dyn SignalSubject>> {
+        self.clone()
+    }
+}
+
+/// Keeps an observer subscribed for as long as the guard is alive.
+///
+/// Dropping the guard unsubscribes the observer. If the subject has already
+/// been dropped, nothing happens.
+#[must_use = "dropping the guard immediately unsubscribes the observer"]
+pub struct SubscriptionGuard {
+    subject: Weak<RefCell<dyn SignalSubject>>,
+    observer: Option<Rc<RefCell<dyn SignalObserver>>>
+}
+
+impl SubscriptionGuard {
+    pub fn unsubscribe(self) {}
+
+    /// Releases the guard without unsubscribing, leaving the observer attached.
+    pub fn forget(mut self) {
+        self.observer = None;
+    }
+}
+
+impl Drop for SubscriptionGuard {
+    fn drop(&mut self) {
+        if let (Some(observer), Some(subject)) = (self.observer.take(), self.subject.upgrade()) {
+            subject.borrow_mut().unsubscribe_observer(&observer);
+        }
+    }
+}
+
+/// A pending signal that a `SignalQueue` can hold and execute. Implement it
+/// to queue a custom snapshot type, e.g. one that carries extra metadata.
+pub trait Executable {
+    /// The event this signal delivers, used for queue diagnostics such as
+    /// `SignalQueue::histogram`. Signals that do not carry an
+    /// `Rc<dyn Event>` can leave this as `None`, and are then left out of
+    /// the histogram and shown without an event name.
+    fn event(&self) -> Option<&Rc<dyn Event>> {
+        None
+    }
+    /// Delivers the signal and returns how many observers were notified.
+    fn execute(&self) -> usize;
+}
+
+impl Executable for SignalSnapShot {
+    fn event(&self) -> Option<&Rc<dyn Event>> {
+        Some(SignalSnapShot::event(self))
+    }
+
+    fn execute(&self) -> usize {
+        SignalSnapShot::execute(self)
+    }
+}
+
+impl Executable for WeakSignalSnapShot {
+    fn event(&self) -> Option<&Rc<dyn Event>> {
+        Some(WeakSignalSnapShot::event(self))
+    }
+
+    fn execute(&self) -> usize {
+        WeakSignalSnapShot::execute(self)
+    }
+}
+
+impl Executable for LazySignalSnapShot {
+    fn event(&self) -> Option<&Rc<dyn Event>> {
+        Some(LazySignalSnapShot::event(self))
+    }
+
+    fn execute(&self) -> usize {
+        LazySignalSnapShot::execute(self)
+    }
+}
+
+/// Cloning a snapshot is cheap: it shares the event and the subscribers.
+///
+/// A snapshot holds strong references, so its subscribers stay alive until it
+/// is dropped, even if nothing else refers to them. Use `downgrade` or
+/// `SignalSubject::get_weak_signal_snapshot` for a snapshot that does not.
+#[derive(Clone)]
+pub struct SignalSnapShot {
+    event:  Rc<dyn Event>,
+    subs: Vec<Rc<RefCell<dyn SignalObserver>>>,
+    origin: Option<Origin>
+}
+
+implement_signal_subject!(SignalSnapShot, subs);
+
+impl SignalSnapShot {
+    /// A snapshot built by hand has no subject of its own, so executing it
+    /// counts as sending from the snapshot itself when checking for loops;
+    /// see `SignalSubject::send_signal`. Snapshots taken from a subject count
+    /// as sent by that subject.
+    pub fn new(event: Rc<dyn Event>, subs: Vec<Rc<RefCell<dyn SignalObserver>>>) -> Self {
+        Self {
+            event,
+            subs,
+            origin: None
+        }
+    }
+
+    pub(crate) fn from_subject(origin: Origin, event: Rc<dyn Event>, subs: Vec<Rc<RefCell<dyn SignalObserver>>>) -> Self {
+        Self {
+            event,
+            subs,
+            origin: Some(origin)
+        }
+    }
+
+    pub fn event(&self) -> &Rc<dyn Event> {
+        &self.event
+    }
+
+    pub fn subscribers(&self) -> &[Rc<RefCell<dyn SignalObserver>>] {
+        &self.subs
+    }
+
+    /// Replaces the carried event with the one `f` returns, keeping the same
+    /// subscribers. The replacement is still an `Rc<dyn Event>` and may be of
+    /// a different concrete type.
+    pub fn map_event<F: FnOnce(Rc<dyn Event>) -> Rc<dyn Event>>(self, f: F) -> SignalSnapShot {
+        SignalSnapShot {
+            event: f(self.event),
+            ..self
+        }
+    }
+
+    /// Keeps only the subscribers for which `f` returns true.
+    pub fn filter_subscribers<F: FnMut(&Rc<RefCell<dyn SignalObserver>>) -> bool>(&mut self, f: F) {
+        self.subs.retain(f);
+    }
+
+    /// Combines two snapshots of the same event into one, keeping each distinct
+    /// subscriber once, in order of first appearance.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the snapshots do not carry the same event `Rc`.
+    pub fn merge(mut self, other: SignalSnapShot) -> SignalSnapShot {
+        assert!(
+            Rc::ptr_eq(&self.event, &other.event),
+            "SignalSnapShot::merge called on snapshots of different events"
+        );
+        append_unique_observers(&mut self.subs, &other.subs);
+        self
+    }
+
+    /// Delivers the event to every subscriber and returns how many were
+    /// notified.
+    pub fn execute(&self) -> usize {
+        let origin = self.origin.unwrap_or_else(|| Origin::of(self));
+        dispatch_signal(origin, &self.subs, self.event.clone())
+    }
+
+    /// Converts into a snapshot that only holds its subscribers weakly.
+    pub fn downgrade(&self) -> WeakSignalSnapShot {
+        WeakSignalSnapShot {
+            event: self.event.clone(),
+            subs: self.subs.iter().map(Rc::downgrade).collect(),
+            origin: self.origin.unwrap_or_else(|| Origin::of(self))
+        }
+    }
+}
+
+/// Like `SignalSnapShot`, but holding its subscribers weakly, so queuing it
+/// does not keep them alive. Subscribers dropped before it executes are
+/// skipped.
+#[derive(Clone)]
+pub struct WeakSignalSnapShot {
+    event: Rc<dyn Event>,
+    subs: Vec<Weak<RefCell<dyn SignalObserver>>>,
+    origin: Origin
+}
+
+impl WeakSignalSnapShot {
+    pub fn event(&self) -> &Rc<dyn Event> {
+        &self.event
+    }
+
+    /// How many subscribers have been dropped and would be skipped.
+    pub fn dead_count(&self) -> usize {
+        self.subs.iter().filter(|x| x.strong_count() == 0).count()
+    }
+
+    /// Delivers the event to every subscriber still alive and returns how many
+    /// were notified.
+    pub fn execute(&self) -> usize {
+        let live: Vec<Rc<RefCell<dyn SignalObserver>>> = self.subs.iter().filter_map(Weak::upgrade).collect();
+        dispatch_signal(self.origin, &live, self.event.clone())
+    }
+}
+
+impl fmt::Debug for WeakSignalSnapShot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WeakSignalSnapShot")
+            .field("event", &EventName::of(&*self.event))
+            .field("subscribers", &self.subs.len())
+            .field("dead", &self.dead_count())
+            .finish()
+    }
+}
+
+/// An event whose observers are looked up from its subject at execution
+/// time, created by `SignalSubjectExt::get_lazy_signal_snapshot`.
+///
+/// Where a `SignalSnapShot` notifies exactly the observers subscribed when it
+/// was taken, a lazy snapshot notifies those subscribed when it executes, and
+/// so includes late subscribers and skips ones that have unsubscribed since.
+/// It holds the subject weakly and notifies nobody once the subject has been
+/// dropped.
+pub struct LazySignalSnapShot {
+    event: Rc<dyn Event>,
+    subject: Weak<RefCell<dyn SignalSubject>>
+}
+
+impl LazySignalSnapShot {
+    pub fn event(&self) -> &Rc<dyn Event> {
+        &self.event
+    }
+
+    /// Delivers the event to the subject's current observers and returns how
+    /// many were notified. The subject is only borrowed while its observers
+    /// are copied, so observers are free to reach back into it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the subject is mutably borrowed.
+    pub fn execute(&self) -> usize {
+        match self.subject.upgrade() {
+            Some(subject) => {
+                let (origin, observers) = {
+                    let subject = subject.borrow();
+                    (Origin::of(&*subject), subject.copy_observers())
+                };
+                dispatch_signal(origin, &observers, self.event.clone())
+            }
+            None => 0
+        }
+    }
+}
+
+impl fmt::Debug for LazySignalSnapShot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LazySignalSnapShot")
+            .field("event", &EventName::of(&*self.event))
+            .field("subject_alive", &(self.subject.strong_count() > 0))
+            .finish()
+    }
+}
+
+impl fmt::Debug for SignalSnapShot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SignalSnapShot")
+            .field("event", &EventName::of(&*self.event))
+            .field("subscribers", &self.subs.len())
+            .finish()
+    }
+}
+
+/// What a bounded `SignalQueue` does with a push that would exceed its
+/// capacity.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OverflowPolicy {
+    /// Leave the queue unchanged and hand the pushed signal back.
+    Reject,
+    /// Evict the signal at the front of the queue to make room.
+    DropOldest,
+    /// Evict the signal at the back of the queue, the one most recently
+    /// pushed, to make room.
+    DropNewest
+}
+
+/// Counters a `SignalQueue` keeps over its lifetime, returned by
+/// `SignalQueue::stats`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct QueueStats {
+    /// Signals accepted into the queue.
+    pub pushed: u64,
+    /// Signals discarded by the queue's `OverflowPolicy`.
+    pub dropped: u64,
+    /// Signals executed by `next_signal` and the methods built on it.
+    pub executed: u64,
+    /// Observers notified across every executed signal.
+    pub observers_notified: u64,
+    /// The most signals the queue has held at once.
+    pub high_water_mark: usize
+}
+
+/// Pending signals per event type, returned by `SignalQueue::histogram`.
+pub type EventTypeCounts = alloc::collections::BTreeMap<TypeId, usize>;
+
+/// Holds signals until they are executed, in FIFO order unless created with
+/// `lifo`. Any `Executable` can be queued; the default is `SignalSnapShot`.
+///
+/// # Ownership and reference cycles
+///
+/// Everything here is reference counted, so a cycle of `Rc`s is never freed.
+/// The layout that avoids them is:
+///
+/// - The application owns its subjects and queues.
+/// - Subjects own their observers. Use `subscribe_weak_observer` for an
+///   observer the subject should not keep alive.
+/// - Observers refer to their subject, or to anything that owns it, through
+///   a `Weak`; an `Rc` back to the subject is a cycle.
+/// - A queued `SignalSnapShot` keeps its subscribers alive until it runs or
+///   is dropped. A queue owned by one of those subscribers, or by a subject
+///   they reach, is a cycle until it is drained. Queue `WeakSignalSnapShot`s
+///   or `LazySignalSnapShot`s instead, which hold no strong references to
+///   observers.
+///
+/// ```
+/// # use std::any::Any;
+/// # use std::cell::RefCell;
+/// # use std::rc::{Rc, Weak};
+/// # use easy_events::{Event, implement_event};
+/// # use easy_signals::{EventBus, SignalObserver, SignalQueue, SignalSubject};
+/// struct Tick;
+/// implement_event!(Tick, "tick");
+///
+/// struct Player {
+///     bus: Weak<RefCell<EventBus>>
+/// }
+///
+/// impl SignalObserver for Player {
+///     fn process_signal(&mut self, _event: Rc<dyn Event>) {
+///         if let Some(bus) = self.bus.upgrade() {
+///             assert!(bus.borrow().has_observers());
+///         }
+///     }
+/// }
+///
+/// let bus = EventBus::new();
+/// let player = Rc::new(RefCell::new(Player { bus: Rc::downgrade(&bus) }));
+/// bus.borrow_mut().subscribe_observer(player.clone());
+///
+/// let queue = SignalQueue::new();
+/// queue.push(bus.borrow().get_weak_signal_snapshot(Rc::new(Tick)));
+///
+/// let (weak_bus, weak_player) = (Rc::downgrade(&bus), Rc::downgrade(&player));
+/// drop(player);
+/// drop(bus);
+/// assert_eq!(weak_bus.strong_count(), 0);
+/// assert_eq!(weak_player.strong_count(), 0);
+///
+/// // The queued signal still runs, but reaches nobody.
+/// assert_eq!(queue.process_all(), 1);
+/// assert_eq!(queue.stats().observers_notified, 0);
+/// ```
+pub struct SignalQueue<S = SignalSnapShot> {
+    signal_queue: RefCell<VecDeque<S>>,
+    capacity: Option<usize>,
+    overflow: OverflowPolicy,
+    lifo: bool,
+    stats: Cell<QueueStats>
+}
+
+impl<S: Executable> SignalQueue<S> {
+    pub fn new() -> Self {
+        Self {
+            signal_queue: RefCell::new(VecDeque::new()),
+            capacity: None,
+            overflow: OverflowPolicy::Reject,
+            lifo: false,
+            stats: Cell::new(QueueStats::default())
+        }
+    }
+
+    /// Creates an unbounded queue with room for `capacity` signals before it
+    /// reallocates.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            signal_queue: RefCell::new(VecDeque::with_capacity(capacity)),
+            ..Self::new()
+        }
+    }
+
+    /// Creates a queue that executes the most recently pushed signal first.
+    /// Signals are still pushed to the back and `push_front` still pushes to
+    /// the front, but `next_signal`, `peek` and `drain_iter` take from the
+    /// back, so a signal pushed with `push_front` executes last.
+    pub fn lifo() -> Self {
+        Self {
+            lifo: true,
+            ..Self::new()
+        }
+    }
+
+    /// Creates a queue holding at most `capacity` signals, applying `overflow`
+    /// when a push would exceed it.
+    ///
+    /// This is not called `with_capacity`, as that name follows `Vec` and
+    /// `VecDeque` and is taken by the constructor that only preallocates.
+    pub fn bounded(capacity: usize, overflow: OverflowPolicy) -> Self {
+        Self {
+            signal_queue: RefCell::new(VecDeque::with_capacity(capacity)),
+            capacity: Some(capacity),
+            overflow,
+            lifo: false,
+            stats: Cell::new(QueueStats::default())
+        }
+    }
+
+    /// Queues a signal. On a bounded queue that is full, returns the signal
+    /// that was dropped under the queue's `OverflowPolicy`: the pushed signal
+    /// for `Reject`, the evicted front signal for `DropOldest` and the evicted
+    /// back signal for `DropNewest`.
+    pub fn push(&self, signal: S) -> Option<S> {
+        self.insert(signal, false)
+    }
+
+    /// Pushes each signal in order. On a bounded queue, signals dropped by the
+    /// overflow policy are discarded.
+    pub fn extend<I: IntoIterator<Item = S>>(&self, signals: I) {
+        for signal in signals {
+            self.push(signal);
+        }
+    }
+
+    /// Moves every signal from `other` onto the back of this queue, in the
+    /// order they were queued there, leaving `other` empty. On a bounded
+    /// queue, signals dropped by the overflow policy are discarded, as in
+    /// `extend`.
+    pub fn append(&self, other: &SignalQueue<S>) {
+        if core::ptr::eq(self, other) {
+            return;
+        }
+        let mut moved = core::mem::take(&mut *other.signal_queue.borrow_mut());
+        if self.capacity.is_some() {
+            self.extend(moved);
+            return;
+        }
+        let mut queue = self.signal_queue.borrow_mut();
+        let mut stats = self.stats.get();
+        stats.pushed += moved.len() as u64;
+        queue.append(&mut moved);
+        stats.high_water_mark = stats.high_water_mark.max(queue.len());
+        self.stats.set(stats);
+    }
+
+    /// Queues a signal at the front of the queue, so it is the next to
+    /// execute on a FIFO queue. A `lifo` queue executes from the back, so
+    /// there it is the last to execute. Capacity is handled as in `push`.
+    pub fn push_front(&self, signal: S) -> Option<S> {
+        self.insert(signal, true)
+    }
+
+    /// Like `push_coalesced`, but two signals are equivalent when `key`
+    /// returns equal values for them.
+    pub fn push_coalesced_by<K: PartialEq, F: Fn(&S) -> K>(&self, signal: S, key: F) -> Option<S> {
+        let signal_key = key(&signal);
+        if self.signal_queue.borrow().iter().any(|s| key(s) == signal_key) {
+            return Some(signal);
+        }
+        self.push(signal)
+    }
+
+    fn insert(&self, signal: S, front: bool) -> Option<S> {
+        let mut queue = self.signal_queue.borrow_mut();
+        let mut stats = self.stats.get();
+        let mut dropped = None;
+        if let Some(capacity) = self.capacity {
+            if queue.len() >= capacity {
+                stats.dropped += 1;
+                dropped = match self.overflow {
+                    OverflowPolicy::Reject => None,
+                    OverflowPolicy::DropOldest => queue.pop_front(),
+                    OverflowPolicy::DropNewest => queue.pop_back()
+                };
+                if dropped.is_none() {
+                    self.stats.set(stats);
+                    return Some(signal);
+                }
+            }
+        }
+        let pushed = EventName::of_signal(&signal);
+        if front {
+            queue.push_front(signal);
+        } else {
+            queue.push_back(signal);
+        }
+        stats.pushed += 1;
+        stats.high_water_mark = stats.high_water_mark.max(queue.len());
+        self.stats.set(stats);
+        trace!(target: "easy_signals::queue", "pushed {:?}, {} queued", pushed, queue.len());
+        dropped
+    }
+
+    fn pop(&self) -> Option<S> {
+        if self.lifo {
+            self.pop_back()
+        } else {
+            self.pop_front()
+        }
+    }
+
+    /// Removes the signal at the front of the queue without executing it.
+    /// This is the next signal `next_signal` would execute, unless the queue
+    /// is `lifo`.
+    pub fn pop_front(&self) -> Option<S> {
+        let mut queue = self.signal_queue.borrow_mut();
+        let signal = queue.pop_front();
+        if let Some(signal) = &signal {
+            trace!(target: "easy_signals::queue", "popped {:?}, {} queued", EventName::of_signal(signal), queue.len());
+        }
+        signal
+    }
+
+    /// Removes the most recently pushed signal without executing it. This is
+    /// the next signal `next_signal` would execute on a `lifo` queue.
+    pub fn pop_back(&self) -> Option<S> {
+        let mut queue = self.signal_queue.borrow_mut();
+        let signal = queue.pop_back();
+        if let Some(signal) = &signal {
+            trace!(target: "easy_signals::queue", "popped {:?}, {} queued", EventName::of_signal(signal), queue.len());
+        }
+        signal
+    }
+
+    /// Borrows the next signal without executing or removing it. The queue
+    /// cannot be pushed to or popped while the returned `Ref` is alive.
+    pub fn peek(&self) -> Option<Ref<'_, S>> {
+        Ref::filter_map(self.signal_queue.borrow(), |q| if self.lifo { q.back() } else { q.front() }).ok()
+    }
+
+    /// Executes the next pending signal and returns it afterwards, so the
+    /// caller can see which event just ran.
+    ///
+    /// ```
+    /// # use std::any::Any;
+    /// # use std::rc::Rc;
+    /// # use easy_events::{Event, implement_event};
+    /// # use easy_signals::{EventBus, SignalQueue, SignalSubject};
+    /// struct Damage(u32);
+    /// implement_event!(Damage, "damage");
+    ///
+    /// let bus = EventBus::new();
+    /// let queue = SignalQueue::new();
+    /// queue.push(bus.borrow().get_signal_snapshot(Rc::new(Damage(3))));
+    ///
+    /// if let Some(s) = queue.next_signal() {
+    ///     if let Some(damage) = s.event().as_any().downcast_ref::<Damage>() {
+    ///         assert_eq!(damage.0, 3);
+    ///     }
+    /// }
+    /// ```
+    pub fn next_signal(&self) -> Option<S> {
+        if let Some(s) = self.pop() {
+            let notified = s.execute();
+            let mut stats = self.stats.get();
+            stats.executed += 1;
+            stats.observers_notified += notified as u64;
+            self.stats.set(stats);
+            Some(s)
+        } else {
+            None
+        }
+    }
+
+    /// Executes signals until the queue is empty, including any pushed while
+    /// processing, and returns how many ran. There is no guard against
+    /// observers that keep pushing new signals; use `process_all_bounded` when
+    /// that can happen.
+    pub fn process_all(&self) -> usize {
+        let mut count = 0;
+        while self.next_signal().is_some() {
+            count += 1;
+        }
+        count
+    }
+
+    /// Like `process_all`, but stops after `max` signals, leaving the rest
+    /// queued. Equivalent to `process_n(max)`.
+    pub fn process_all_bounded(&self, max: usize) -> usize {
+        self.process_n(max)
+    }
+
+    /// Executes up to `n` signals and returns how many ran, fewer if the queue
+    /// drained first. Signals pushed while processing count against the same
+    /// budget.
+    pub fn process_n(&self, n: usize) -> usize {
+        let mut count = 0;
+        while count < n && self.next_signal().is_some() {
+            count += 1;
+        }
+        count
+    }
+
+    /// Discards every pending signal without executing it.
+    pub fn clear(&self) {
+        self.signal_queue.borrow_mut().clear();
+    }
+
+    /// Removes and returns every pending signal without executing it, in the
+    /// order `next_signal` would have executed them, so last-pushed first on a
+    /// `lifo` queue.
+    pub fn drain(&self) -> Vec<S> {
+        let mut queue = self.signal_queue.borrow_mut();
+        if self.lifo {
+            queue.drain(..).rev().collect()
+        } else {
+            queue.drain(..).collect()
+        }
+    }
+
+    /// Lazily pops signals without executing them, until the queue is empty.
+    /// Signals pushed while iterating are yielded too.
+    pub fn drain_iter(&self) -> impl Iterator<Item = S> + '_ {
+        core::iter::from_fn(move || self.pop())
+    }
+
+    /// Removes every pending signal for which `f` returns false, without
+    /// executing it. The remaining signals keep their order.
+    pub fn retain<F: FnMut(&S) -> bool>(&self, f: F) {
+        self.signal_queue.borrow_mut().retain(f);
+    }
+
+    /// Counts the pending signals by the concrete type of their event. Signals
+    /// without an event, see `Executable::event`, are not counted.
+    pub fn histogram(&self) -> EventTypeCounts {
+        let mut counts = EventTypeCounts::new();
+        for event in self.signal_queue.borrow().iter().filter_map(Executable::event) {
+            *counts.entry(event.as_any().type_id()).or_insert(0) += 1;
+        }
+        counts
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.signal_queue.borrow().is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.signal_queue.borrow().len()
+    }
+
+    pub fn stats(&self) -> QueueStats {
+        self.stats.get()
+    }
+}
+
+impl SignalQueue {
+    /// Queues a signal unless an equivalent one is already queued, in which
+    /// case the pushed signal is handed back. Two signals are equivalent when
+    /// their events have the same concrete type and they have the same
+    /// subscribers, by `Rc` identity and in the same order.
+    pub fn push_coalesced(&self, signal: SignalSnapShot) -> Option<SignalSnapShot> {
+        self.push_coalesced_by(signal, |s| {
+            let subscribers: Vec<*const ()> = s.subscribers().iter().map(|o| Rc::as_ptr(o) as *const ()).collect();
+            (s.event().as_any().type_id(), subscribers)
+        })
+    }
+
+    /// Whether any pending signal would notify `observer`, directly or through
+    /// a wrapper such as `PrioritizedObserver`.
+    pub fn contains_observer(&self, observer: &Rc<RefCell<dyn SignalObserver>>) -> bool {
+        self.signal_queue.borrow().iter().any(|s| s.subscribers().iter().any(|x| same_observer(x, observer)))
+    }
+
+    /// Removes `observer` from every pending signal, for example before it is
+    /// torn down. Signals left with no subscribers stay queued.
+    pub fn remove_observer_from_pending(&self, observer: &Rc<RefCell<dyn SignalObserver>>) {
+        for s in self.signal_queue.borrow_mut().iter_mut() {
+            s.filter_subscribers(|x| !same_observer(x, observer));
+        }
+    }
+}
+
+impl<S: Executable> Default for SignalQueue<S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S: Executable> fmt::Debug for SignalQueue<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut debug = f.debug_struct("SignalQueue");
+        match self.signal_queue.try_borrow() {
+            Ok(queue) => {
+                let events: Vec<EventName> = queue.iter().map(EventName::of_signal).collect();
+                debug.field("len", &queue.len()).field("events", &events);
+            }
+            Err(_) => {
+                debug.field("len", &"<borrowed>");
+            }
+        }
+        debug.field("capacity", &self.capacity)
+            .field("overflow", &self.overflow)
+            .field("lifo", &self.lifo)
+            .finish()
+    }
+}
+
+impl<S: Executable> Extend<S> for SignalQueue<S> {
+    fn extend<I: IntoIterator<Item = S>>(&mut self, signals: I) {
+        SignalQueue::extend(self, signals);
+    }
+}
+
+impl<S: Executable> Extend<S> for &SignalQueue<S> {
+    fn extend<I: IntoIterator<Item = S>>(&mut self, signals: I) {
+        SignalQueue::extend(self, signals);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::any::Any;
+    use std::rc::Weak;
+    use easy_self_referencing_objects::{SelfReferencing, implement_self_referencing};
+    use easy_events::implement_event;
+    
+    struct Observer {
+        me: Weak<RefCell<Self>>
+    }
+
+    impl Observer {
+        fn new() -> Rc<RefCell<Self>> {
+            Rc::new_cyclic(|me| {
+                RefCell::new(Self { me: me.clone() })
+            })
+        }
+    }
+
+    implement_self_referencing!(Observer, me);
+
+    impl SignalObserver for Observer {
+        fn process_signal(&mut self, event: Rc<dyn Event>) {
+            if let Some(_e) = event.as_any().downcast_ref::<EventA>() {
+                println!("Observer received EventA");
+            }
+
+            if let Some(_e) = event.as_any().downcast_ref::<EventB>() {
+                println!("Observer received EventB");
+            }
+
+            if let Some(_e) = event.as_any().downcast_ref::<EventC>() {
+                println!("Observer received EventC");
+            }
+        }
+    }
+
+    struct ObserverBackground {
+        me: Weak<RefCell<Self>>
+    }
+
+    impl ObserverBackground {
+        fn new() -> Rc<RefCell<Self>> {
+            Rc::new_cyclic(|me| {
+                RefCell::new(Self { me: me.clone() })
+            })
+        }
+    }
+
+    implement_self_referencing!(ObserverBackground, me);
+
+    impl SignalObserver for ObserverBackground {
+        fn process_signal(&mut self, event: Rc<dyn Event>) {
+            if let Some(_e) = event.as_any().downcast_ref::<EventA>() {
+                println!("Background Observer received EventA");
+            }
+
+            if let Some(_e) = event.as_any().downcast_ref::<EventB>() {
+                println!("Background Observer received EventB");
+            }
+
+            if let Some(_e) = event.as_any().downcast_ref::<EventC>() {
+                println!("Background Observer received EventC");
+            }
+        }
+    }
+    
+    struct Subject {
+        me: Weak<RefCell<Self>>,
+        subs: Vec<Rc<RefCell<dyn SignalObserver>>>
+    }
+
+    impl Subject {
+        fn new() -> Rc<RefCell<Self>> {
+            Rc::new_cyclic(|me| {
+                RefCell::new(Self { 
+                    me: me.clone(),
+                    subs: Vec::new()
+                 })
+            })
+        }
+    }
+
+    implement_self_referencing!(Subject, me);
+    implement_signal_subject!(Subject, subs);
+
+    struct CountingObserver {
+        received: usize
+    }
+
+    impl CountingObserver {
+        fn new() -> Rc<RefCell<Self>> {
+            Rc::new(RefCell::new(Self { received: 0 }))
+        }
+    }
+
+    impl SignalObserver for CountingObserver {
+        fn process_signal(&mut self, _event: Rc<dyn Event>) {
+            self.received += 1;
+        }
+    }
+
+    struct FailingObserver;
+
+    impl SignalObserver for FailingObserver {
+        fn process_signal(&mut self, event: Rc<dyn Event>) {
+            let _ = self.try_process_signal(event);
+        }
+
+        fn try_process_signal(&mut self, _event: Rc<dyn Event>) -> Result<(), SignalError> {
+            Err("observer failed".into())
+        }
+    }
+
+    struct SwallowingObserver;
+
+    impl SignalObserver for SwallowingObserver {
+        fn process_signal(&mut self, _event: Rc<dyn Event>) {}
+
+        fn process_signal_interruptible(&mut self, event: Rc<dyn Event>) -> ControlFlow<()> {
+            if event.as_any().is::<EventA>() {
+                ControlFlow::Break(())
+            } else {
+                ControlFlow::Continue(())
+            }
+        }
+    }
+
+    struct RequeueingObserver {
+        me: Weak<RefCell<Self>>,
+        queue: Rc<SignalQueue>,
+        remaining: usize
+    }
+
+    impl RequeueingObserver {
+        fn new(queue: Rc<SignalQueue>, remaining: usize) -> Rc<RefCell<Self>> {
+            Rc::new_cyclic(|me| {
+                RefCell::new(Self {
+                    me: me.clone(),
+                    queue,
+                    remaining
+                })
+            })
+        }
+    }
+
+    impl SignalObserver for RequeueingObserver {
+        fn process_signal(&mut self, event: Rc<dyn Event>) {
+            if self.remaining > 0 {
+                self.remaining -= 1;
+                self.queue.push(SignalSnapShot::new(event, vec![self.me.upgrade().unwrap()]));
+            }
+        }
+    }
+
+    struct OrderObserver {
+        id: u32,
+        log: Rc<RefCell<Vec<u32>>>
+    }
+
+    impl OrderObserver {
+        fn new(id: u32, log: &Rc<RefCell<Vec<u32>>>) -> Rc<RefCell<Self>> {
+            Rc::new(RefCell::new(Self { id, log: log.clone() }))
+        }
+    }
+
+    impl SignalObserver for OrderObserver {
+        fn process_signal(&mut self, _event: Rc<dyn Event>) {
+            self.log.borrow_mut().push(self.id);
+        }
+    }
+
+    struct SpawningObserver {
+        subject: Weak<RefCell<Subject>>,
+        spawned: Option<Rc<RefCell<CountingObserver>>>
+    }
+
+    impl SignalObserver for SpawningObserver {
+        fn process_signal(&mut self, _event: Rc<dyn Event>) {
+            if self.spawned.is_none() {
+                let spawned = CountingObserver::new();
+                self.subject.upgrade().unwrap().borrow_mut().subscribe_observer(spawned.clone());
+                self.spawned = Some(spawned);
+            }
+        }
+    }
+
+    struct EventA;
+    implement_event!(EventA, "event_a");
+
+    struct EventB;
+    implement_event!(EventB, "event_b");
+
+    struct EventC;
+    implement_event!(EventC, "event_c");
+
+    #[test]
+    fn add_and_signal_observer() {
+        let subject = Subject::new();
+        let observer = Observer::new();
+
+        assert_eq!(subject.borrow().send_signal(Rc::new(EventA{})), 0);
+
+        subject.borrow_mut().subscribe_observer(observer.clone());
+        assert_eq!(subject.borrow().send_signal(Rc::new(EventA{})), 1);
+        assert_eq!(subject.borrow().send_signal(Rc::new(EventB{})), 1);
+    }
+
+    #[test]
+    fn queue_test() {
+        let queue = SignalQueue::new();
+        let subject = Subject::new();
+        let observer = Observer::new();
+
+        subject.borrow_mut().subscribe_observer(observer.clone());
+
+        queue.push(subject.borrow().get_signal_snapshot(Rc::new(EventA{})));
+        queue.next_signal();
+
+        queue.push(subject.borrow().get_signal_snapshot(Rc::new(EventB{})));
+        queue.push(subject.borrow().get_signal_snapshot(Rc::new(EventA{})));
+        queue.next_signal();
+        queue.push(subject.borrow().get_signal_snapshot(Rc::new(EventB{})));
+        assert_eq!(queue.len(), 2);
+        queue.next_signal();
+        queue.next_signal();
+        assert_eq!(queue.len(), 0);
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn targeted_signal_test() {
+        let queue = SignalQueue::new();
+        let subject = Subject::new();
+        let observer_target = Observer::new();
+        let observer_background = ObserverBackground::new();
+
+        subject.borrow_mut().subscribe_observer(observer_background.clone());
+
+        subject.borrow().send_signal(Rc::new(EventA{}));
+        
+        assert_eq!(subject.borrow().send_signal_to(Rc::new(EventB{}), &vec![observer_target.clone()]), 2);
+
+        queue.push(subject.borrow().get_signal_to_snapshot(Rc::new(EventC{}), &vec![observer_target.clone()]));
+        queue.next_signal();
+    }
+
+    #[test]
+    fn unsubscribe_observer_test() {
+        let subject = Subject::new();
+        let observer_a = Observer::new();
+        let observer_b = ObserverBackground::new();
+        let observer_a_dyn: Rc<RefCell<dyn SignalObserver>> = observer_a.clone();
+        let observer_b_dyn: Rc<RefCell<dyn SignalObserver>> = observer_b.clone();
+
+        subject.borrow_mut().subscribe_observer(observer_a.clone());
+        subject.borrow_mut().subscribe_observer(observer_b.clone());
+
+        assert!(subject.borrow_mut().unsubscribe_observer(&observer_a_dyn));
+        assert!(!subject.borrow_mut().unsubscribe_observer(&observer_a_dyn));
+        assert!(!subject.borrow().is_subscribed(&observer_a_dyn));
+        assert!(subject.borrow().is_subscribed(&observer_b_dyn));
+        assert_eq!(subject.borrow().observer_count(), 1);
+        assert!(Rc::ptr_eq(subject.borrow().get_observers_iter().next().unwrap(), &observer_b_dyn));
+
+        subject.borrow().send_signal(Rc::new(EventA{}));
+    }
+
+    #[test]
+    fn observer_count_test() {
+        let subject = Subject::new();
+        assert_eq!(subject.borrow().observer_count(), 0);
+        assert!(!subject.borrow().has_observers());
+
+        subject.borrow_mut().subscribe_observer(Observer::new());
+        subject.borrow_mut().subscribe_observer(ObserverBackground::new());
+        assert_eq!(subject.borrow().observer_count(), 2);
+        assert!(subject.borrow().has_observers());
+    }
+
+    #[test]
+    fn subscription_guard_test() {
+        let subject = Subject::new();
+
+        let guard = subject.subscribe_observer_scoped(Observer::new());
+        assert_eq!(subject.borrow().observer_count(), 1);
+        drop(guard);
+        assert_eq!(subject.borrow().observer_count(), 0);
+
+        let guard = subject.subscribe_observer_scoped(Observer::new());
+        guard.unsubscribe();
+        assert_eq!(subject.borrow().observer_count(), 0);
+
+        let guard = subject.subscribe_observer_scoped(Observer::new());
+        guard.forget();
+        assert_eq!(subject.borrow().observer_count(), 1);
+
+        let guard = subject.subscribe_observer_scoped(ObserverBackground::new());
+        drop(subject);
+        drop(guard);
+    }
+
+    #[test]
+    fn subscribe_during_dispatch_test() {
+        let subject = Subject::new();
+        let spawner = Rc::new(RefCell::new(SpawningObserver {
+            subject: Rc::downgrade(&subject),
+            spawned: None
+        }));
+
+        subject.borrow_mut().subscribe_observer(spawner.clone());
+
+        assert_eq!(subject.emit(Rc::new(EventA{})), 1);
+
+        let spawned = spawner.borrow().spawned.clone().unwrap();
+        assert_eq!(subject.borrow().observer_count(), 2);
+        assert_eq!(spawned.borrow().received, 0);
+
+        assert_eq!(subject.emit(Rc::new(EventB{})), 2);
+        assert_eq!(spawned.borrow().received, 1);
+        assert_eq!(subject.borrow().observer_count(), 2);
+    }
+
+    #[test]
+    fn targeted_signal_dedup_test() {
+        let queue = SignalQueue::new();
+        let subject = Subject::new();
+        let counter = CountingObserver::new();
+
+        subject.borrow_mut().subscribe_observer(counter.clone());
+
+        let targets: Vec<Rc<RefCell<dyn SignalObserver>>> = vec![counter.clone(), counter.clone()];
+        assert_eq!(subject.borrow().send_signal_to(Rc::new(EventA{}), &targets), 1);
+        assert_eq!(counter.borrow().received, 1);
+
+        queue.push(subject.borrow().get_signal_to_snapshot(Rc::new(EventB{}), &targets));
+        queue.next_signal();
+        assert_eq!(counter.borrow().received, 2);
+    }
+
+    #[test]
+    fn clear_observers_test() {
+        let subject = Subject::new();
+        let counter = CountingObserver::new();
+
+        subject.borrow_mut().subscribe_observer(counter.clone());
+        subject.borrow_mut().subscribe_observer(Observer::new());
+
+        assert_eq!(subject.borrow_mut().clear_observers(), 2);
+        assert!(!subject.borrow().has_observers());
+        assert_eq!(subject.borrow().send_signal(Rc::new(EventA{})), 0);
+        assert_eq!(counter.borrow().received, 0);
+        assert_eq!(subject.borrow_mut().clear_observers(), 0);
+    }
+
+    #[test]
+    fn try_send_signal_test() {
+        let subject = Subject::new();
+        let counter = CountingObserver::new();
+
+        subject.borrow_mut().subscribe_observer(Rc::new(RefCell::new(FailingObserver)));
+        subject.borrow_mut().subscribe_observer(counter.clone());
+        subject.borrow_mut().subscribe_observer(Rc::new(RefCell::new(FailingObserver)));
+
+        let errors = subject.borrow().try_send_signal(Rc::new(EventA{}));
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors[0].to_string(), "observer failed");
+        assert_eq!(counter.borrow().received, 1);
+    }
+
+    #[test]
+    fn send_signal_interruptible_test() {
+        let subject = Subject::new();
+        let before = CountingObserver::new();
+        let after = CountingObserver::new();
+
+        subject.borrow_mut().subscribe_observer(before.clone());
+        subject.borrow_mut().subscribe_observer(Rc::new(RefCell::new(SwallowingObserver)));
+        subject.borrow_mut().subscribe_observer(after.clone());
+
+        assert_eq!(subject.borrow().send_signal_interruptible(Rc::new(EventA{})), 2);
+        assert_eq!(before.borrow().received, 1);
+        assert_eq!(after.borrow().received, 0);
+
+        assert_eq!(subject.borrow().send_signal_interruptible(Rc::new(EventB{})), 3);
+        assert_eq!(before.borrow().received, 2);
+        assert_eq!(after.borrow().received, 1);
+    }
+
+    #[test]
+    fn process_all_test() {
+        let queue = Rc::new(SignalQueue::new());
+        let subject = Subject::new();
+
+        subject.borrow_mut().subscribe_observer(RequeueingObserver::new(queue.clone(), 3));
+        queue.push(subject.borrow().get_signal_snapshot(Rc::new(EventA{})));
+        queue.push(subject.borrow().get_signal_snapshot(Rc::new(EventB{})));
+
+        assert_eq!(queue.process_all(), 5);
+        assert!(queue.is_empty());
+        assert_eq!(queue.process_all(), 0);
+    }
+
+    #[test]
+    fn process_all_bounded_test() {
+        let queue = Rc::new(SignalQueue::new());
+        let subject = Subject::new();
+
+        subject.borrow_mut().subscribe_observer(RequeueingObserver::new(queue.clone(), usize::MAX));
+        queue.push(subject.borrow().get_signal_snapshot(Rc::new(EventA{})));
+
+        assert_eq!(queue.process_all_bounded(10), 10);
+        assert_eq!(queue.len(), 1);
+    }
+
+    #[test]
+    fn peek_test() {
+        let queue = SignalQueue::new();
+        let subject = Subject::new();
+        let counter = CountingObserver::new();
+
+        assert!(queue.peek().is_none());
+
+        subject.borrow_mut().subscribe_observer(counter.clone());
+        queue.push(subject.borrow().get_signal_snapshot(Rc::new(EventB{})));
+        queue.push(subject.borrow().get_signal_snapshot(Rc::new(EventA{})));
+
+        assert!(queue.peek().unwrap().event().as_any().is::<EventB>());
+        assert_eq!(queue.len(), 2);
+        assert_eq!(counter.borrow().received, 0);
+
+        queue.next_signal();
+        assert!(queue.peek().unwrap().event().as_any().is::<EventA>());
+    }
+
+    #[test]
+    fn clear_and_drain_test() {
+        let queue = SignalQueue::new();
+        let subject = Subject::new();
+        let counter = CountingObserver::new();
+
+        subject.borrow_mut().subscribe_observer(counter.clone());
+        for _ in 0..3 {
+            queue.push(subject.borrow().get_signal_snapshot(Rc::new(EventA{})));
+        }
+        queue.clear();
+        assert!(queue.is_empty());
+        assert!(queue.next_signal().is_none());
+        assert_eq!(counter.borrow().received, 0);
+
+        queue.push(subject.borrow().get_signal_snapshot(Rc::new(EventA{})));
+        queue.push(subject.borrow().get_signal_snapshot(Rc::new(EventB{})));
+        let drained = queue.drain();
+        assert!(queue.is_empty());
+        assert_eq!(drained.len(), 2);
+        assert!(drained[1].event().as_any().is::<EventB>());
+        assert_eq!(counter.borrow().received, 0);
+    }
+
+    #[test]
+    fn snapshot_accessors_test() {
+        let subject = Subject::new();
+        let observer: Rc<RefCell<dyn SignalObserver>> = CountingObserver::new();
+
+        subject.borrow_mut().subscribe_observer(observer.clone());
+        let snapshot = subject.borrow().get_signal_snapshot(Rc::new(EventC{}));
+
+        assert!(snapshot.event().as_any().is::<EventC>());
+        assert_eq!(snapshot.subscribers().len(), 1);
+        assert!(Rc::ptr_eq(&snapshot.subscribers()[0], &observer));
+        assert_eq!(snapshot.execute(), 1);
+        assert_eq!(SignalSnapShot::new(Rc::new(EventA{}), Vec::new()).execute(), 0);
+    }
+
+    #[test]
+    fn snapshot_merge_test() {
+        let event: Rc<dyn Event> = Rc::new(EventA{});
+        let counter_a = CountingObserver::new();
+        let counter_b = CountingObserver::new();
+
+        let first = SignalSnapShot::new(event.clone(), vec![counter_a.clone(), counter_b.clone()]);
+        let second = SignalSnapShot::new(event.clone(), vec![counter_b.clone(), counter_a.clone()]);
+        let merged = first.merge(second);
+
+        assert_eq!(merged.subscribers().len(), 2);
+        assert_eq!(merged.execute(), 2);
+        assert_eq!(counter_a.borrow().received, 1);
+        assert_eq!(counter_b.borrow().received, 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "different events")]
+    fn snapshot_merge_different_events_test() {
+        let first = SignalSnapShot::new(Rc::new(EventA{}), Vec::new());
+        let second = SignalSnapShot::new(Rc::new(EventA{}), Vec::new());
+        first.merge(second);
+    }
+
+    #[test]
+    fn priority_order_test() {
+        let subject = Subject::new();
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let low: Rc<RefCell<dyn SignalObserver>> = OrderObserver::new(4, &log);
+
+        subject.borrow_mut().subscribe_observer(OrderObserver::new(1, &log));
+        subject.borrow_mut().subscribe_observer_with_priority(low.clone(), -5);
+        subject.borrow_mut().subscribe_observer_with_priority(OrderObserver::new(2, &log), 10);
+        subject.borrow_mut().subscribe_observer(OrderObserver::new(3, &log));
+        subject.borrow_mut().subscribe_observer_with_priority(OrderObserver::new(5, &log), 10);
+
+        subject.borrow().send_signal(Rc::new(EventA{}));
+        assert_eq!(*log.borrow(), vec![2, 5, 1, 3, 4]);
+
+        assert!(subject.borrow().is_subscribed(&low));
+        subject.borrow_mut().subscribe_observer_with_priority(low.clone(), 20);
+        assert_eq!(subject.borrow().observer_count(), 5);
+
+        log.borrow_mut().clear();
+        subject.borrow().send_signal(Rc::new(EventA{}));
+        assert_eq!(*log.borrow(), vec![4, 2, 5, 1, 3]);
+
+        assert!(subject.borrow_mut().unsubscribe_observer(&low));
+        assert_eq!(subject.borrow().observer_count(), 4);
+    }
+
+    #[test]
+    fn weak_observer_test() {
+        let subject = Subject::new();
+        let counter = CountingObserver::new();
+        let weak_counter: Rc<RefCell<dyn SignalObserver>> = counter.clone();
+
+        subject.borrow_mut().subscribe_weak_observer(Rc::downgrade(&weak_counter));
+        drop(weak_counter);
+        assert!(subject.borrow().is_subscribed(&(counter.clone() as Rc<RefCell<dyn SignalObserver>>)));
+        assert_eq!(subject.borrow().send_signal(Rc::new(EventA{})), 1);
+        assert_eq!(counter.borrow().received, 1);
+
+        drop(counter);
+        assert_eq!(subject.borrow().observer_count(), 1);
+        assert_eq!(subject.borrow().send_signal(Rc::new(EventA{})), 0);
+        assert_eq!(subject.borrow_mut().prune_dead_observers(), 1);
+        assert!(!subject.borrow().has_observers());
+
+        let dropped: Rc<RefCell<dyn SignalObserver>> = CountingObserver::new();
+        subject.borrow_mut().subscribe_weak_observer(Rc::downgrade(&dropped));
+        drop(dropped);
+        subject.borrow_mut().subscribe_observer(Observer::new());
+        assert_eq!(subject.borrow().observer_count(), 1);
+    }
+
+    #[test]
+    fn send_signal_except_test() {
+        let subject = Subject::new();
+        let sender = CountingObserver::new();
+        let listener = CountingObserver::new();
+        let excluded: [Rc<RefCell<dyn SignalObserver>>; 1] = [sender.clone()];
+
+        subject.borrow_mut().subscribe_observer(sender.clone());
+        subject.borrow_mut().subscribe_observer(listener.clone());
+
+        assert_eq!(subject.borrow().send_signal_except(Rc::new(EventA{}), &excluded), 1);
+        assert_eq!(sender.borrow().received, 0);
+        assert_eq!(listener.borrow().received, 1);
+
+        let snapshot = subject.borrow().get_signal_except_snapshot(Rc::new(EventA{}), &excluded);
+        assert_eq!(snapshot.execute(), 1);
+        assert_eq!(sender.borrow().received, 0);
+        assert_eq!(listener.borrow().received, 2);
+    }
+
+    #[test]
+    fn send_signal_where_test() {
+        let subject = Subject::new();
+        let active = CountingObserver::new();
+        let inactive = CountingObserver::new();
+        let active_dyn: Rc<RefCell<dyn SignalObserver>> = active.clone();
+
+        subject.borrow_mut().subscribe_observer(active.clone());
+        subject.borrow_mut().subscribe_observer(inactive.clone());
+
+        let count = subject.borrow().send_signal_where(Rc::new(EventA{}), |o| Rc::ptr_eq(o, &active_dyn));
+        assert_eq!(count, 1);
+        assert_eq!(active.borrow().received, 1);
+        assert_eq!(inactive.borrow().received, 0);
+
+        let snapshot = subject.borrow().get_signal_where_snapshot(Rc::new(EventA{}), |o| !Rc::ptr_eq(o, &active_dyn));
+        assert_eq!(snapshot.execute(), 1);
+        assert_eq!(active.borrow().received, 1);
+        assert_eq!(inactive.borrow().received, 1);
+    }
+
+    #[test]
+    fn bounded_queue_test() {
+        let subject = Subject::new();
+        subject.borrow_mut().subscribe_observer(CountingObserver::new());
+
+        let queue = SignalQueue::bounded(2, OverflowPolicy::Reject);
+        assert!(queue.push(subject.borrow().get_signal_snapshot(Rc::new(EventA{}))).is_none());
+        assert!(queue.push(subject.borrow().get_signal_snapshot(Rc::new(EventA{}))).is_none());
+        let rejected = queue.push(subject.borrow().get_signal_snapshot(Rc::new(EventB{})));
+        assert!(rejected.unwrap().event().as_any().is::<EventB>());
+        assert_eq!(queue.len(), 2);
+
+        let queue = SignalQueue::bounded(2, OverflowPolicy::DropOldest);
+        queue.push(subject.borrow().get_signal_snapshot(Rc::new(EventA{})));
+        queue.push(subject.borrow().get_signal_snapshot(Rc::new(EventB{})));
+        let dropped = queue.push(subject.borrow().get_signal_snapshot(Rc::new(EventC{})));
+        assert!(dropped.unwrap().event().as_any().is::<EventA>());
+        assert_eq!(queue.len(), 2);
+        assert!(queue.peek().unwrap().event().as_any().is::<EventB>());
+
+        let queue = SignalQueue::bounded(2, OverflowPolicy::DropNewest);
+        queue.push(subject.borrow().get_signal_snapshot(Rc::new(EventA{})));
+        queue.push(subject.borrow().get_signal_snapshot(Rc::new(EventB{})));
+        let dropped = queue.push(subject.borrow().get_signal_snapshot(Rc::new(EventC{})));
+        assert!(dropped.unwrap().event().as_any().is::<EventB>());
+        assert_eq!(queue.len(), 2);
+        assert_eq!(queue.stats().dropped, 1);
+        assert!(queue.next_signal().unwrap().event().as_any().is::<EventA>());
+        assert!(queue.next_signal().unwrap().event().as_any().is::<EventC>());
+    }
+
+    #[test]
+    fn push_front_test() {
+        let queue = SignalQueue::new();
+
+        queue.push(SignalSnapShot::new(Rc::new(EventA{}), Vec::new()));
+        queue.push(SignalSnapShot::new(Rc::new(EventB{}), Vec::new()));
+        queue.push_front(SignalSnapShot::new(Rc::new(EventC{}), Vec::new()));
+
+        assert!(queue.next_signal().unwrap().event().as_any().is::<EventC>());
+        assert!(queue.next_signal().unwrap().event().as_any().is::<EventA>());
+        assert!(queue.next_signal().unwrap().event().as_any().is::<EventB>());
+    }
+
+    #[test]
+    fn push_coalesced_test() {
+        let queue = SignalQueue::new();
+        let subject = Subject::new();
+        let counter = CountingObserver::new();
+
+        subject.borrow_mut().subscribe_observer(counter.clone());
+        for _ in 0..3 {
+            queue.push_coalesced(subject.borrow().get_signal_snapshot(Rc::new(EventA{})));
+        }
+        assert!(queue.push_coalesced(subject.borrow().get_signal_snapshot(Rc::new(EventB{}))).is_none());
+        assert!(queue.push_coalesced(SignalSnapShot::new(Rc::new(EventA{}), Vec::new())).is_none());
+        assert_eq!(queue.len(), 3);
+
+        assert!(queue.push_coalesced_by(SignalSnapShot::new(Rc::new(EventC{}), Vec::new()), |s| s.subscribers().len()).is_some());
+        assert_eq!(queue.len(), 3);
+
+        queue.process_all();
+        assert_eq!(counter.borrow().received, 2);
+    }
+
+    mod macro_hygiene {
+        struct BareSubject {
+            subs: Vec<std::rc::Rc<std::cell::RefCell<dyn crate::SignalObserver>>>
+        }
+
+        implement_signal_subject!(BareSubject, subs,);
+
+        #[test]
+        fn macro_needs_no_imports_test() {
+            use crate::SignalSubject;
+
+            let mut subject = BareSubject { subs: Vec::new() };
+            let observer: std::rc::Rc<std::cell::RefCell<dyn crate::SignalObserver>> = super::CountingObserver::new();
+            subject.subscribe_observer(observer.clone());
+            assert!(subject.unsubscribe_observer(&observer));
+            assert_eq!(subject.clear_observers(), 0);
+        }
+    }
+
+    #[cfg(feature = "smallvec")]
+    #[derive(Default)]
+    struct SmallSubject {
+        subs: SmallObserverList
+    }
+
+    #[cfg(feature = "smallvec")]
+    implement_signal_subject!(SmallSubject, subs);
+
+    #[test]
+    #[cfg(feature = "smallvec")]
+    fn small_observer_list_test() {
+        let mut subject = SmallSubject::default();
+        let first = CountingObserver::new();
+        let second = CountingObserver::new();
+        subject.subscribe_observer(first.clone());
+        subject.subscribe_observer_with_priority(second.clone(), 1);
+        subject.subscribe_observer(first.clone());
+        assert_eq!(subject.observer_count(), 2);
+        assert!(!subject.subs.spilled());
+
+        assert_eq!(subject.send_signal(Rc::new(EventA{})), 2);
+        assert_eq!(first.borrow().received, 1);
+        assert_eq!(second.borrow().received, 1);
+
+        let first_dyn: Rc<RefCell<dyn SignalObserver>> = first;
+        assert!(subject.unsubscribe_observer(&first_dyn));
+        assert_eq!(subject.drain_observers().len(), 1);
+        assert!(!subject.has_observers());
+    }
+
+    #[test]
+    fn filtered_subscription_test() {
+        let subject = Subject::new();
+        let only_a = CountingObserver::new();
+        let a_or_c = CountingObserver::new();
+
+        subject.borrow_mut().subscribe_observer_for::<EventA>(only_a.clone());
+        subject.borrow_mut().subscribe_observer_filtered(a_or_c.clone(), &[TypeId::of::<EventA>(), TypeId::of::<EventC>()]);
+
+        assert_eq!(subject.borrow().send_signal(Rc::new(EventA{})), 2);
+        assert_eq!(subject.borrow().send_signal(Rc::new(EventB{})), 0);
+        assert_eq!(subject.borrow().send_signal(Rc::new(EventC{})), 1);
+        assert_eq!(only_a.borrow().received, 1);
+        assert_eq!(a_or_c.borrow().received, 2);
+    }
+
+    #[test]
+    fn process_n_test() {
+        let queue = Rc::new(SignalQueue::new());
+        let subject = Subject::new();
+
+        subject.borrow_mut().subscribe_observer(RequeueingObserver::new(queue.clone(), 2));
+        queue.push(subject.borrow().get_signal_snapshot(Rc::new(EventA{})));
+
+        assert_eq!(queue.process_n(2), 2);
+        assert_eq!(queue.len(), 1);
+        assert_eq!(queue.process_n(5), 1);
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn extend_and_drain_iter_test() {
+        let queue = SignalQueue::new();
+        let counter = CountingObserver::new();
+        let batch = vec![
+            SignalSnapShot::new(Rc::new(EventA{}), vec![counter.clone()]),
+            SignalSnapShot::new(Rc::new(EventB{}), vec![counter.clone()])
+        ];
+
+        queue.extend(batch);
+        Extend::extend(&mut &queue, std::iter::once(SignalSnapShot::new(Rc::new(EventC{}), Vec::new())));
+        assert_eq!(queue.len(), 3);
+
+        let mut drained = queue.drain_iter();
+        assert!(drained.next().unwrap().event().as_any().is::<EventA>());
+        assert_eq!(drained.count(), 2);
+        assert!(queue.is_empty());
+        assert_eq!(counter.borrow().received, 0);
+    }
+
+    #[test]
+    fn delivery_order_test() {
+        let subject = Subject::new();
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let first: Rc<RefCell<dyn SignalObserver>> = OrderObserver::new(1, &log);
+
+        subject.borrow_mut().subscribe_observer(first.clone());
+        subject.borrow_mut().subscribe_observer(OrderObserver::new(2, &log));
+        subject.borrow_mut().subscribe_observer(OrderObserver::new(3, &log));
+
+        subject.borrow().send_signal(Rc::new(EventA{}));
+        assert_eq!(*log.borrow(), vec![1, 2, 3]);
+
+        log.borrow_mut().clear();
+        subject.borrow().send_signal_reversed(Rc::new(EventA{}));
+        assert_eq!(*log.borrow(), vec![3, 2, 1]);
+
+        log.borrow_mut().clear();
+        subject.borrow_mut().subscribe_observer(first.clone());
+        subject.borrow().send_signal(Rc::new(EventA{}));
+        assert_eq!(*log.borrow(), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn debug_format_test() {
+        let queue = SignalQueue::new();
+        let snapshot = SignalSnapShot::new(Rc::new(EventA{}), vec![CountingObserver::new()]);
+
+        let formatted = format!("{:?}", snapshot);
+        assert!(formatted.starts_with("SignalSnapShot"));
+        assert!(formatted.contains(&format!("{:?}", TypeId::of::<EventA>())));
+        assert!(formatted.contains("subscribers: 1"));
+
+        queue.push(snapshot);
+        queue.push(SignalSnapShot::new(Rc::new(EventB{}), Vec::new()));
+        let formatted = format!("{:?}", queue);
+        assert!(formatted.contains("len: 2"));
+        assert!(formatted.contains(&format!("{:?}", TypeId::of::<EventB>())));
+        assert!(formatted.contains("overflow: Reject"));
+        assert!(formatted.contains("lifo: false"));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn debug_format_event_name_test() {
+        struct Named;
+        implement_event!(Named, "named");
+
+        hooks::register_event_name::<Named>();
+        let snapshot = SignalSnapShot::new(Rc::new(Named), Vec::new());
+        assert!(format!("{:?}", snapshot).contains(std::any::type_name::<Named>()));
+        assert!(format!("{:?}", snapshot.downgrade()).contains(std::any::type_name::<Named>()));
+
+        let queue = SignalQueue::new();
+        queue.push(snapshot);
+        assert!(format!("{:?}", queue).contains(&format!("events: [{}]", std::any::type_name::<Named>())));
+    }
+
+    #[test]
+    fn retain_observers_test() {
+        let subject = Subject::new();
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let hud: Vec<Rc<RefCell<dyn SignalObserver>>> = vec![OrderObserver::new(2, &log), OrderObserver::new(4, &log)];
+
+        subject.borrow_mut().subscribe_observer(OrderObserver::new(1, &log));
+        subject.borrow_mut().subscribe_observer(hud[0].clone());
+        subject.borrow_mut().subscribe_observer(OrderObserver::new(3, &log));
+        subject.borrow_mut().subscribe_observer(hud[1].clone());
+
+        subject.borrow_mut().retain_observers(|o| !hud.iter().any(|h| Rc::ptr_eq(h, o)));
+        assert_eq!(subject.borrow().observer_count(), 2);
+
+        subject.borrow().send_signal(Rc::new(EventA{}));
+        assert_eq!(*log.borrow(), vec![1, 3]);
+    }
+
+    #[test]
+    fn subscribe_many_test() {
+        let subject = Subject::new();
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let first: Rc<RefCell<dyn SignalObserver>> = OrderObserver::new(1, &log);
+
+        subject.borrow_mut().subscribe_many(vec![
+            first.clone(),
+            OrderObserver::new(2, &log),
+            first.clone(),
+            OrderObserver::new(3, &log)
+        ]);
+        assert_eq!(subject.borrow().observer_count(), 3);
+
+        subject.borrow().send_signal(Rc::new(EventA{}));
+        assert_eq!(*log.borrow(), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn observer_capacity_test() {
+        let subject = Subject::new();
+        subject.borrow_mut().reserve_observers(32);
+        assert!(subject.borrow().subs.capacity() >= 32);
+
+        subject.borrow_mut().subscribe_observer(CountingObserver::new());
+        subject.borrow_mut().shrink_observers_to_fit();
+        assert!(subject.borrow().subs.capacity() < 32);
+        assert_eq!(subject.borrow().observer_count(), 1);
+    }
+
+    #[test]
+    fn queue_stats_test() {
+        let subject = Subject::new();
+        subject.borrow_mut().subscribe_observer(CountingObserver::new());
+        subject.borrow_mut().subscribe_observer(CountingObserver::new());
+        let queue = SignalQueue::bounded(2, OverflowPolicy::Reject);
+
+        for _ in 0..3 {
+            queue.push(subject.borrow().get_signal_snapshot(Rc::new(EventA{})));
+        }
+        queue.next_signal();
+        queue.push(subject.borrow().get_signal_snapshot(Rc::new(EventA{})));
+        queue.process_all();
+
+        assert_eq!(queue.stats(), QueueStats {
+            pushed: 3,
+            dropped: 1,
+            executed: 3,
+            observers_notified: 6,
+            high_water_mark: 2
+        });
+    }
+
+    #[test]
+    fn lazy_signal_snapshot_test() {
+        let subject = Subject::new();
+        let early = CountingObserver::new();
+        let late = CountingObserver::new();
+        subject.borrow_mut().subscribe_observer(early.clone());
+
+        let eager = subject.borrow().get_signal_snapshot(Rc::new(EventA{}));
+        let lazy = subject.get_lazy_signal_snapshot(Rc::new(EventA{}));
+        subject.borrow_mut().subscribe_observer(late.clone());
+
+        assert_eq!(eager.execute(), 1);
+        assert_eq!(lazy.execute(), 2);
+        assert_eq!(early.borrow().received, 2);
+        assert_eq!(late.borrow().received, 1);
+
+        drop(subject);
+        assert_eq!(lazy.execute(), 0);
+    }
+
+    struct SourceObserver {
+        sources: Vec<Rc<RefCell<dyn SignalSubject>>>
+    }
+
+    impl SignalObserver for SourceObserver {
+        fn process_signal(&mut self, _event: Rc<dyn Event>) {}
+
+        fn process_signal_from(&mut self, _event: Rc<dyn Event>, source: Rc<RefCell<dyn SignalSubject>>) {
+            self.sources.push(source);
+        }
+    }
+
+    #[test]
+    fn send_signal_with_source_test() {
+        let left = Subject::new();
+        let right = Subject::new();
+        let observer = Rc::new(RefCell::new(SourceObserver { sources: Vec::new() }));
+        let counter = CountingObserver::new();
+
+        left.borrow_mut().subscribe_observer_with_priority(observer.clone(), 1);
+        left.borrow_mut().subscribe_observer(counter.clone());
+        right.borrow_mut().subscribe_observer(observer.clone());
+
+        assert_eq!(left.borrow().send_signal_with_source(Rc::new(EventA{}), left.as_signal_subject()), 2);
+        right.borrow().send_signal_with_source(Rc::new(EventA{}), right.as_signal_subject());
+
+        let sources = &observer.borrow().sources;
+        assert_eq!(sources.len(), 2);
+        assert!(Rc::ptr_eq(&sources[0], &left.as_signal_subject()));
+        assert!(Rc::ptr_eq(&sources[1], &right.as_signal_subject()));
+        assert_eq!(counter.borrow().received, 1);
+    }
+
+    #[test]
+    fn filter_subscribers_test() {
+        let kept = CountingObserver::new();
+        let disabled = CountingObserver::new();
+        let disabled_dyn: Rc<RefCell<dyn SignalObserver>> = disabled.clone();
+
+        let mut snapshot = SignalSnapShot::new(Rc::new(EventA{}), vec![kept.clone(), disabled.clone()]);
+        snapshot.filter_subscribers(|o| !Rc::ptr_eq(o, &disabled_dyn));
+        assert_eq!(snapshot.subscribers().len(), 1);
+
+        assert_eq!(snapshot.execute(), 1);
+        assert_eq!(kept.borrow().received, 1);
+        assert_eq!(disabled.borrow().received, 0);
+    }
+
+    #[test]
+    fn map_event_test() {
+        let counter = CountingObserver::new();
+        let snapshot = SignalSnapShot::new(Rc::new(EventA{}), vec![counter.clone()]);
+
+        let mapped = snapshot.map_event(|event| {
+            assert!(event.as_any().is::<EventA>());
+            Rc::new(EventB{})
+        });
+        assert!(mapped.event().as_any().is::<EventB>());
+        assert_eq!(mapped.execute(), 1);
+        assert_eq!(counter.borrow().received, 1);
+    }
+
+    #[test]
+    fn resubscribe_keeps_position_test() {
+        let subject = Subject::new();
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let a: Rc<RefCell<dyn SignalObserver>> = OrderObserver::new(1, &log);
+        let b: Rc<RefCell<dyn SignalObserver>> = OrderObserver::new(2, &log);
+
+        subject.borrow_mut().subscribe_observer(a.clone());
+        subject.borrow_mut().subscribe_observer(b.clone());
+        subject.borrow_mut().subscribe_observer(a.clone());
+        assert_eq!(subject.borrow().observer_count(), 2);
+
+        subject.borrow().send_signal(Rc::new(EventA{}));
+        assert_eq!(*log.borrow(), vec![1, 2]);
+
+        log.borrow_mut().clear();
+        subject.borrow_mut().subscribe_observer_for::<EventB>(a.clone());
+        subject.borrow().send_signal(Rc::new(EventA{}));
+        subject.borrow().send_signal(Rc::new(EventB{}));
+        assert_eq!(*log.borrow(), vec![2, 1, 2]);
+    }
+
+    #[test]
+    fn replace_observer_test() {
+        let subject = Subject::new();
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let old: Rc<RefCell<dyn SignalObserver>> = OrderObserver::new(2, &log);
+        let third: Rc<RefCell<dyn SignalObserver>> = OrderObserver::new(3, &log);
+
+        subject.borrow_mut().subscribe_observer(OrderObserver::new(1, &log));
+        subject.borrow_mut().subscribe_observer(old.clone());
+        subject.borrow_mut().subscribe_observer(third.clone());
+
+        assert!(subject.borrow_mut().replace_observer(&old, OrderObserver::new(4, &log)));
+        assert!(!subject.borrow().is_subscribed(&old));
+        assert!(!subject.borrow_mut().replace_observer(&old, OrderObserver::new(5, &log)));
+
+        subject.borrow().send_signal(Rc::new(EventA{}));
+        assert_eq!(*log.borrow(), vec![1, 4, 3]);
+
+        let first = subject.borrow().copy_observers()[0].clone();
+        assert!(subject.borrow_mut().replace_observer(&first, third.clone()));
+        assert_eq!(subject.borrow().observer_count(), 2);
+
+        log.borrow_mut().clear();
+        subject.borrow().send_signal(Rc::new(EventA{}));
+        assert_eq!(*log.borrow(), vec![3, 4]);
+    }
+
+    #[test]
+    fn send_signals_test() {
+        let subject = Subject::new();
+        let log = Rc::new(RefCell::new(Vec::new()));
+        subject.borrow_mut().subscribe_observer(OrderObserver::new(1, &log));
+        subject.borrow_mut().subscribe_observer(OrderObserver::new(2, &log));
+
+        let events: Vec<Rc<dyn Event>> = vec![Rc::new(EventA{}), Rc::new(EventB{}), Rc::new(EventC{})];
+        assert_eq!(subject.borrow().send_signals(&events), 6);
+        assert_eq!(*log.borrow(), vec![1, 2, 1, 2, 1, 2]);
+        assert_eq!(subject.borrow().send_signals(&[]), 0);
+    }
+
+    struct LifecycleObserver {
+        subject: Option<Weak<RefCell<dyn SignalSubject>>>,
+        subscribed_count: usize,
+        unsubscribed: bool
+    }
+
+    impl SignalObserver for LifecycleObserver {
+        fn process_signal(&mut self, _event: Rc<dyn Event>) {}
+
+        fn on_subscribe(&mut self, subject: &Rc<RefCell<dyn SignalSubject>>) {
+            self.subscribed_count = subject.borrow().observer_count();
+            self.subject = Some(Rc::downgrade(subject));
+        }
+
+        fn on_unsubscribe(&mut self) {
+            self.unsubscribed = true;
+        }
+    }
+
+    #[test]
+    fn lifecycle_callbacks_test() {
+        let subject = Subject::new();
+        let observer = Rc::new(RefCell::new(LifecycleObserver { subject: None, subscribed_count: 0, unsubscribed: false }));
+        let observer_dyn: Rc<RefCell<dyn SignalObserver>> = observer.clone();
+
+        subject.borrow_mut().subscribe_observer(CountingObserver::new());
+        subject.attach_observer(PrioritizedObserver::new(observer.clone(), 5));
+        assert_eq!(observer.borrow().subscribed_count, 2);
+        assert!(Rc::ptr_eq(&observer.borrow().subject.as_ref().unwrap().upgrade().unwrap(), &subject.as_signal_subject()));
+
+        assert!(subject.borrow_mut().unsubscribe_observer(&observer_dyn));
+        assert!(observer.borrow().unsubscribed);
+    }
+
+    #[test]
+    fn lifecycle_callbacks_wrapped_test() {
+        let subject = Subject::new();
+        let observer = Rc::new(RefCell::new(LifecycleObserver { subject: None, subscribed_count: 0, unsubscribed: false }));
+
+        subject.borrow_mut().subscribe_once(observer.clone());
+        assert_eq!(observer.borrow().subscribed_count, 0);
+
+        subject.borrow_mut().clear_observers();
+        subject.attach_observer(OnceObserver::new(observer.clone()));
+        assert_eq!(observer.borrow().subscribed_count, 1);
+    }
+
+    #[test]
+    fn lifecycle_removal_paths_test() {
+        fn lifecycle() -> Rc<RefCell<LifecycleObserver>> {
+            Rc::new(RefCell::new(LifecycleObserver { subject: None, subscribed_count: 0, unsubscribed: false }))
+        }
+        let subject = Subject::new();
+
+        let observer = lifecycle();
+        subject.borrow_mut().subscribe_observer(observer.clone());
+        assert_eq!(subject.borrow_mut().clear_observers(), 1);
+        assert!(observer.borrow().unsubscribed);
+
+        let observer = lifecycle();
+        subject.borrow_mut().subscribe_observer(observer.clone());
+        assert_eq!(subject.borrow_mut().drain_observers().len(), 1);
+        assert!(observer.borrow().unsubscribed);
+
+        let (kept, removed) = (lifecycle(), lifecycle());
+        let removed_dyn: Rc<RefCell<dyn SignalObserver>> = removed.clone();
+        subject.borrow_mut().subscribe_observer(kept.clone());
+        subject.borrow_mut().subscribe_observer(removed.clone());
+        subject.borrow_mut().retain_observers(|x| !same_observer(x, &removed_dyn));
+        assert!(removed.borrow().unsubscribed);
+        assert!(!kept.borrow().unsubscribed);
+
+        let observer = lifecycle();
+        subject.borrow_mut().subscribe_observer_tagged(observer.clone(), "hud");
+        assert_eq!(subject.borrow_mut().unsubscribe_group("hud"), 1);
+        assert!(observer.borrow().unsubscribed);
+
+        let observer = lifecycle();
+        subject.borrow_mut().subscribe_once(observer.clone());
+        subject.borrow().send_signal(Rc::new(EventA{}));
+        assert!(!observer.borrow().unsubscribed);
+        assert_eq!(subject.borrow_mut().prune_dead_observers(), 1);
+        assert!(observer.borrow().unsubscribed);
+
+        let (old, new) = (lifecycle(), lifecycle());
+        let old_dyn: Rc<RefCell<dyn SignalObserver>> = old.clone();
+        subject.borrow_mut().subscribe_observer(old.clone());
+        assert!(subject.borrow_mut().replace_observer(&old_dyn, new.clone()));
+        assert!(old.borrow().unsubscribed);
+        assert!(!new.borrow().unsubscribed);
+        assert!(!kept.borrow().unsubscribed);
+    }
+
+    #[test]
+    fn copy_observers_invariants_test() {
+        let subject = Subject::new();
+        let a: Rc<RefCell<dyn SignalObserver>> = CountingObserver::new();
+        let b: Rc<RefCell<dyn SignalObserver>> = CountingObserver::new();
+        let c: Rc<RefCell<dyn SignalObserver>> = CountingObserver::new();
+
+        subject.borrow_mut().subscribe_observer(a.clone());
+        subject.borrow_mut().subscribe_observer(b.clone());
+        subject.borrow_mut().subscribe_observer(a.clone());
+        subject.borrow_mut().subscribe_once(b.clone());
+        subject.borrow_mut().subscribe_observer(c.clone());
+
+        let copy = subject.borrow().copy_observers();
+        assert_eq!(copy.len(), 3);
+        for (observer, expected) in copy.iter().zip([&a, &b, &c]) {
+            assert!(same_observer(observer, expected));
+        }
+        assert!(Rc::ptr_eq(&copy[0], &a));
+        assert!(Rc::ptr_eq(&copy[2], &c));
+
+        subject.borrow_mut().subs.push(a.clone());
+        subject.borrow_mut().subs.push(PrioritizedObserver::new(c.clone(), 0));
+        assert_eq!(subject.borrow().copy_observers().len(), 5);
+
+        let unique = subject.borrow().unique_observers();
+        assert_eq!(unique.len(), 3);
+        assert!(Rc::ptr_eq(&unique[0], &a));
+        assert!(Rc::ptr_eq(&unique[2], &c));
+    }
+
+    #[test]
+    fn lifo_queue_test() {
+        let queue = SignalQueue::lifo();
+
+        queue.push(SignalSnapShot::new(Rc::new(EventA{}), Vec::new()));
+        queue.push(SignalSnapShot::new(Rc::new(EventB{}), Vec::new()));
+        queue.push(SignalSnapShot::new(Rc::new(EventC{}), Vec::new()));
+
+        assert!(queue.peek().unwrap().event().as_any().is::<EventC>());
+        assert!(queue.next_signal().unwrap().event().as_any().is::<EventC>());
+        assert!(queue.next_signal().unwrap().event().as_any().is::<EventB>());
+        assert!(queue.next_signal().unwrap().event().as_any().is::<EventA>());
+        assert!(queue.next_signal().is_none());
+
+        queue.push(SignalSnapShot::new(Rc::new(EventA{}), Vec::new()));
+        queue.push_front(SignalSnapShot::new(Rc::new(EventC{}), Vec::new()));
+        queue.push(SignalSnapShot::new(Rc::new(EventB{}), Vec::new()));
+        assert!(queue.next_signal().unwrap().event().as_any().is::<EventB>());
+        assert!(queue.next_signal().unwrap().event().as_any().is::<EventA>());
+        assert!(queue.next_signal().unwrap().event().as_any().is::<EventC>());
 
-pub trait SignalSubject {
-    fn get_observers_iter(&self) -> Iter<Rc<RefCell<dyn SignalObserver>>>;
-    fn subscribe_observer(&mut self, new_observer: Rc<RefCell<dyn SignalObserver>>);
-    fn copy_observers(&self) -> Vec<Rc<RefCell<dyn SignalObserver>>> {
-        let mut copy = Vec::new();
-        for o in self.get_observers_iter() {
-            copy.push(o.clone());
-        }
-        copy
+        queue.push(SignalSnapShot::new(Rc::new(EventA{}), Vec::new()));
+        queue.push(SignalSnapShot::new(Rc::new(EventB{}), Vec::new()));
+        let drained = queue.drain();
+        assert!(drained[0].event().as_any().is::<EventB>());
+        assert!(drained[1].event().as_any().is::<EventA>());
     }
-    fn send_signal(&self, event: Rc<dyn Event>) {
-        for o in self.get_observers_iter() {
-            o.borrow_mut().process_signal(event.clone());
-        }
+
+    #[test]
+    fn append_queue_test() {
+        let main = SignalQueue::new();
+        let other = SignalQueue::new();
+
+        main.push(SignalSnapShot::new(Rc::new(EventA{}), Vec::new()));
+        other.push(SignalSnapShot::new(Rc::new(EventB{}), Vec::new()));
+        other.push(SignalSnapShot::new(Rc::new(EventC{}), Vec::new()));
+
+        main.append(&other);
+        main.append(&main);
+        assert!(other.is_empty());
+        assert_eq!(main.len(), 3);
+        assert_eq!(main.stats().pushed, 3);
+
+        assert!(main.next_signal().unwrap().event().as_any().is::<EventA>());
+        assert!(main.next_signal().unwrap().event().as_any().is::<EventB>());
+        assert!(main.next_signal().unwrap().event().as_any().is::<EventC>());
     }
-    fn get_signal_snapshot(&self, event: Rc<dyn Event>) -> SignalSnapShot {
-        SignalSnapShot {
-            event,
-            subs: self.copy_observers()
-        }
+
+    #[test]
+    fn tagged_group_test() {
+        let subject = Subject::new();
+        let log = Rc::new(RefCell::new(Vec::new()));
+
+        subject.borrow_mut().subscribe_observer_tagged(OrderObserver::new(1, &log), "hud");
+        subject.borrow_mut().subscribe_observer(OrderObserver::new(2, &log));
+        subject.borrow_mut().subscribe_observer_tagged(PrioritizedObserver::new(OrderObserver::new(3, &log), 5), "hud");
+        subject.borrow_mut().subscribe_observer_with_priority(TaggedObserver::new(OrderObserver::new(4, &log), "world"), -1);
+
+        assert_eq!(subject.borrow().send_signal_to_group(Rc::new(EventA{}), "hud"), 2);
+        assert_eq!(*log.borrow(), vec![3, 1]);
+
+        log.borrow_mut().clear();
+        assert_eq!(subject.borrow_mut().unsubscribe_group("hud"), 2);
+        assert_eq!(subject.borrow_mut().unsubscribe_group("hud"), 0);
+        subject.borrow().send_signal(Rc::new(EventA{}));
+        assert_eq!(*log.borrow(), vec![2, 4]);
     }
-    fn send_signal_to(&self, event: Rc<dyn Event>, targets: &Vec<Rc<RefCell<dyn SignalObserver>>>) {
-        for o in self.get_observers_iter() {
-            o.borrow_mut().process_signal(event.clone());
-        }
-        for o in targets.iter() {
-            o.borrow_mut().process_signal(event.clone());
-        }
+
+    #[test]
+    fn snapshot_clone_test() {
+        let counter = CountingObserver::new();
+        let snapshot = SignalSnapShot::new(Rc::new(EventA{}), vec![counter.clone()]);
+        let replay = snapshot.clone();
+
+        assert!(Rc::ptr_eq(snapshot.event(), replay.event()));
+        assert_eq!(snapshot.execute(), 1);
+        assert_eq!(replay.execute(), 1);
+        assert_eq!(counter.borrow().received, 2);
     }
-    fn get_signal_to_snapshot(&self, event: Rc<dyn Event>, targets: &Vec<Rc<RefCell<dyn SignalObserver>>>) -> SignalSnapShot {
-        let mut subs = self.copy_observers();
-        subs.append(&mut targets.clone());
-        SignalSnapShot {
-            event,
-            subs
-        }
+
+    #[test]
+    fn pop_without_executing_test() {
+        let queue = SignalQueue::new();
+        let counter = CountingObserver::new();
+
+        queue.push(SignalSnapShot::new(Rc::new(EventA{}), vec![counter.clone()]));
+        queue.push(SignalSnapShot::new(Rc::new(EventB{}), vec![counter.clone()]));
+        queue.push(SignalSnapShot::new(Rc::new(EventC{}), vec![counter.clone()]));
+
+        assert!(queue.pop_front().unwrap().event().as_any().is::<EventA>());
+        assert!(queue.pop_back().unwrap().event().as_any().is::<EventC>());
+        assert_eq!(queue.len(), 1);
+        assert_eq!(counter.borrow().received, 0);
+        assert_eq!(queue.stats().executed, 0);
     }
-}
-  
-#[macro_export]
-macro_rules! implement_signal_subject {
-    (
-        $struct:ident, 
-        $observers_field:ident
-    ) => {
-        impl SignalSubject for $struct {
-            fn get_observers_iter(&self) -> Iter<Rc<RefCell<dyn SignalObserver>>> {
-                self.$observers_field.iter()
-            }
 
-            fn subscribe_observer(&mut self, new_observer: Rc<RefCell<dyn SignalObserver>>) {
-                self.$observers_field.retain(|x| !Rc::ptr_eq(x, &new_observer));
-                self.$observers_field.push(new_observer);
+    struct EchoSubject {
+        subs: Vec<Rc<RefCell<dyn SignalObserver>>>,
+        received: usize,
+        echo_errors: usize
+    }
+
+    implement_signal_subject!(EchoSubject, subs);
+
+    impl SignalObserver for EchoSubject {
+        fn process_signal(&mut self, event: Rc<dyn Event>) {
+            self.received += 1;
+            if event.as_any().is::<EventA>() {
+                self.echo_errors += self.try_send_signal(Rc::new(EventB{})).len();
             }
         }
     }
-}
 
-pub struct SignalSnapShot {
-    event:  Rc<dyn Event>,
-    subs: Vec<Rc<RefCell<dyn SignalObserver>>>
-}
+    #[test]
+    fn self_observing_subject_test() {
+        let echo = Rc::new(RefCell::new(EchoSubject { subs: Vec::new(), received: 0, echo_errors: 0 }));
+        let counter = CountingObserver::new();
+        echo.borrow_mut().subscribe_observer(echo.clone());
+        echo.borrow_mut().subscribe_observer_with_priority(counter.clone(), -1);
 
-implement_signal_subject!(SignalSnapShot, subs);
+        let errors = echo.borrow().try_send_signal(Rc::new(EventC{}));
+        assert_eq!(errors.len(), 1);
+        assert_eq!(echo.borrow().received, 0);
+        assert_eq!(counter.borrow().received, 1);
 
-impl SignalSnapShot {
-    pub fn execute(&self) {
-        self.send_signal(self.event.clone());
+        let snapshot = echo.borrow().get_signal_snapshot(Rc::new(EventA{}));
+        assert_eq!(snapshot.execute(), 2);
+        assert_eq!(echo.borrow().received, 1);
+        assert_eq!(echo.borrow().echo_errors, 1);
+        assert_eq!(counter.borrow().received, 3);
+
+        echo.borrow_mut().clear_observers();
     }
-}
 
-pub struct SignalQueue {
-    signal_queue: RefCell<VecDeque<SignalSnapShot>>
-}
+    #[test]
+    fn subscribe_fn_test() {
+        let subject = Subject::new();
+        let calls = Rc::new(Cell::new(0));
+        let c = calls.clone();
+        let handle = subject.borrow_mut().subscribe_fn(Box::new(move |_| c.set(c.get() + 1)));
 
-impl SignalQueue {
-    pub fn new() -> Self {
-        Self {
-            signal_queue: RefCell::new(VecDeque::new())
-        }
-    }
+        subject.borrow().send_signal(Rc::new(EventA{}));
+        assert_eq!(calls.get(), 1);
 
-    pub fn push(&self, signal: SignalSnapShot) {
-        self.signal_queue.borrow_mut().push_back(signal);
+        subject.borrow_mut().unsubscribe_observer(&handle);
+        subject.borrow().send_signal(Rc::new(EventA{}));
+        assert_eq!(calls.get(), 1);
     }
 
-    fn pop(&self) -> Option<SignalSnapShot> {
-        self.signal_queue.borrow_mut().pop_front()
+    #[test]
+    fn send_signal_to_order_test() {
+        let subject = Subject::new();
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let shared: Rc<RefCell<dyn SignalObserver>> = OrderObserver::new(2, &log);
+        subject.borrow_mut().subscribe_observer(OrderObserver::new(1, &log));
+        subject.borrow_mut().subscribe_observer(shared.clone());
+        let targets: [Rc<RefCell<dyn SignalObserver>>; 2] = [OrderObserver::new(3, &log), shared];
+
+        assert_eq!(subject.borrow().send_signal_to(Rc::new(EventA{}), &targets), 3);
+        assert_eq!(*log.borrow(), vec![1, 2, 3]);
+
+        log.borrow_mut().clear();
+        assert_eq!(subject.borrow().send_signal_to_first(Rc::new(EventA{}), &targets), 3);
+        assert_eq!(*log.borrow(), vec![3, 2, 1]);
+
+        log.borrow_mut().clear();
+        assert_eq!(subject.borrow().get_signal_to_first_snapshot(Rc::new(EventA{}), &targets).execute(), 3);
+        assert_eq!(*log.borrow(), vec![3, 2, 1]);
     }
 
-    pub fn next_signal(&self) -> Option<SignalSnapShot> {
-        if let Some(s) = self.pop() {
-            s.execute();
-            Some(s)
-        } else {
-            None
+    #[cfg(feature = "std")]
+    struct PanickingObserver;
+
+    #[cfg(feature = "std")]
+    impl SignalObserver for PanickingObserver {
+        fn process_signal(&mut self, _event: Rc<dyn Event>) {
+            panic!("observer panicked");
         }
     }
 
-    pub fn is_empty(&self) -> bool {
-        self.signal_queue.borrow().is_empty()
+    #[test]
+    #[cfg(feature = "std")]
+    fn send_signal_catch_unwind_test() {
+        let subject = Subject::new();
+        let counter = CountingObserver::new();
+        let panicking: Rc<RefCell<dyn SignalObserver>> = Rc::new(RefCell::new(PanickingObserver));
+
+        subject.borrow_mut().subscribe_observer(panicking.clone());
+        subject.borrow_mut().subscribe_observer(counter.clone());
+
+        let panics = subject.borrow().send_signal_catch_unwind(Rc::new(EventA{}));
+        assert_eq!(panics.len(), 1);
+        assert!(same_observer(&panics[0].0, &panicking));
+        assert_eq!(panics[0].1.downcast_ref::<&str>(), Some(&"observer panicked"));
+        assert_eq!(counter.borrow().received, 1);
+        assert!(panicking.try_borrow_mut().is_ok());
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use std::any::Any;
-    use std::rc::Weak;
-    use easy_self_referencing_objects::{SelfReferencing, implement_self_referencing};
-    use easy_events::implement_event;
-    
-    struct Observer {
-        me: Weak<RefCell<Self>>
+    #[test]
+    fn queue_retain_test() {
+        let subject = Subject::new();
+        let log = Rc::new(RefCell::new(Vec::new()));
+        subject.borrow_mut().subscribe_observer(OrderObserver::new(1, &log));
+        let queue = SignalQueue::new();
+        queue.push(subject.borrow().get_signal_snapshot(Rc::new(EventA{})));
+        queue.push(subject.borrow().get_signal_snapshot(Rc::new(EventB{})));
+        queue.push(subject.borrow().get_signal_snapshot(Rc::new(EventC{})));
+
+        queue.retain(|s| !s.event().as_any().is::<EventB>());
+        assert_eq!(queue.len(), 2);
+
+        assert!(queue.next_signal().unwrap().event().as_any().is::<EventA>());
+        assert!(queue.next_signal().unwrap().event().as_any().is::<EventC>());
+        assert!(queue.next_signal().is_none());
+        assert_eq!(*log.borrow(), vec![1, 1]);
     }
 
-    impl Observer {
-        fn new() -> Rc<RefCell<Self>> {
-            Rc::new_cyclic(|me| {
-                RefCell::new(Self { me: me.clone() })
-            })
-        }
+    #[test]
+    fn queue_histogram_test() {
+        let subject = Subject::new();
+        let queue = SignalQueue::new();
+        assert!(queue.histogram().is_empty());
+
+        queue.push(subject.borrow().get_signal_snapshot(Rc::new(EventA{})));
+        queue.push(subject.borrow().get_signal_snapshot(Rc::new(EventB{})));
+        queue.push(subject.borrow().get_signal_snapshot(Rc::new(EventA{})));
+
+        let counts = queue.histogram();
+        assert_eq!(counts.len(), 2);
+        assert_eq!(counts[&TypeId::of::<EventA>()], 2);
+        assert_eq!(counts[&TypeId::of::<EventB>()], 1);
+        assert_eq!(queue.len(), 3);
     }
 
-    implement_self_referencing!(Observer, me);
+    #[test]
+    fn queue_remove_observer_from_pending_test() {
+        let subject = Subject::new();
+        let counter = CountingObserver::new();
+        let other = CountingObserver::new();
+        let counter_dyn: Rc<RefCell<dyn SignalObserver>> = counter.clone();
+        subject.borrow_mut().subscribe_observer(PrioritizedObserver::new(counter.clone(), 1));
+        subject.borrow_mut().subscribe_observer(other.clone());
 
-    impl SignalObserver for Observer {
-        fn process_signal(&mut self, event: Rc<dyn Event>) {
-            if let Some(_e) = event.as_any().downcast_ref::<EventA>() {
-                println!("Observer received EventA");
-            }
+        let queue = SignalQueue::new();
+        queue.push(subject.borrow().get_signal_snapshot(Rc::new(EventA{})));
+        queue.push(subject.borrow().get_signal_snapshot(Rc::new(EventB{})));
+        assert!(queue.contains_observer(&counter_dyn));
 
-            if let Some(_e) = event.as_any().downcast_ref::<EventB>() {
-                println!("Observer received EventB");
-            }
+        queue.remove_observer_from_pending(&counter_dyn);
+        assert!(!queue.contains_observer(&counter_dyn));
+        assert_eq!(queue.len(), 2);
 
-            if let Some(_e) = event.as_any().downcast_ref::<EventC>() {
-                println!("Observer received EventC");
-            }
-        }
+        queue.process_all();
+        assert_eq!(counter.borrow().received, 0);
+        assert_eq!(other.borrow().received, 2);
     }
 
-    struct ObserverBackground {
-        me: Weak<RefCell<Self>>
-    }
+    #[test]
+    fn send_signal_boxed_test() {
+        let subject = Subject::new();
+        let counter = CountingObserver::new();
+        subject.borrow_mut().subscribe_observer(counter.clone());
 
-    impl ObserverBackground {
-        fn new() -> Rc<RefCell<Self>> {
-            Rc::new_cyclic(|me| {
-                RefCell::new(Self { me: me.clone() })
-            })
-        }
+        let event: Box<dyn Event> = Box::new(EventA{});
+        assert_eq!(subject.borrow().send_signal_boxed(event), 1);
+        assert_eq!(counter.borrow().received, 1);
     }
 
-    implement_self_referencing!(ObserverBackground, me);
+    #[test]
+    fn subject_ext_subscribe_emit_test() {
+        let subject = Subject::new();
+        let counter = CountingObserver::new();
+        let lifecycle = Rc::new(RefCell::new(LifecycleObserver { subject: None, subscribed_count: 0, unsubscribed: false }));
+        subject.subscribe(counter.clone());
+        subject.subscribe(lifecycle.clone());
+        assert_eq!(lifecycle.borrow().subscribed_count, 2);
 
-    impl SignalObserver for ObserverBackground {
-        fn process_signal(&mut self, event: Rc<dyn Event>) {
-            if let Some(_e) = event.as_any().downcast_ref::<EventA>() {
-                println!("Background Observer received EventA");
-            }
+        subject.subscribe(Rc::new(RefCell::new(SpawningObserver { subject: Rc::downgrade(&subject), spawned: None })));
 
-            if let Some(_e) = event.as_any().downcast_ref::<EventB>() {
-                println!("Background Observer received EventB");
-            }
+        assert_eq!(subject.emit(Rc::new(EventA{})), 3);
+        assert_eq!(counter.borrow().received, 1);
+        assert_eq!(subject.borrow().observer_count(), 4);
+        subject.borrow_mut().clear_observers();
+    }
 
-            if let Some(_e) = event.as_any().downcast_ref::<EventC>() {
-                println!("Background Observer received EventC");
-            }
-        }
+    struct LabelledSnapShot {
+        label: &'static str,
+        snapshot: SignalSnapShot,
+        log: Rc<RefCell<Vec<&'static str>>>
     }
-    
-    struct Subject {
-        me: Weak<RefCell<Self>>,
-        subs: Vec<Rc<RefCell<dyn SignalObserver>>>
+
+    impl Executable for LabelledSnapShot {
+        fn event(&self) -> Option<&Rc<dyn Event>> {
+            Some(self.snapshot.event())
+        }
+
+        fn execute(&self) -> usize {
+            self.log.borrow_mut().push(self.label);
+            self.snapshot.execute()
+        }
     }
 
-    impl Subject {
-        fn new() -> Rc<RefCell<Self>> {
-            Rc::new_cyclic(|me| {
-                RefCell::new(Self { 
-                    me: me.clone(),
-                    subs: Vec::new()
-                 })
-            })
+    #[test]
+    fn custom_executable_queue_test() {
+        let subject = Subject::new();
+        let counter = CountingObserver::new();
+        subject.borrow_mut().subscribe_observer(counter.clone());
+        let log = Rc::new(RefCell::new(Vec::new()));
+
+        let queue: SignalQueue<LabelledSnapShot> = SignalQueue::new();
+        for label in ["first", "second"] {
+            queue.push(LabelledSnapShot { label, snapshot: subject.borrow().get_signal_snapshot(Rc::new(EventA{})), log: log.clone() });
         }
+        assert_eq!(queue.histogram()[&TypeId::of::<EventA>()], 2);
+
+        assert_eq!(queue.process_all(), 2);
+        assert_eq!(*log.borrow(), vec!["first", "second"]);
+        assert_eq!(counter.borrow().received, 2);
+        assert_eq!(queue.stats().observers_notified, 2);
+
+        let lazy_queue = SignalQueue::new();
+        lazy_queue.push(subject.get_lazy_signal_snapshot(Rc::new(EventA{})));
+        subject.borrow_mut().subscribe_observer(CountingObserver::new());
+        assert_eq!(lazy_queue.process_all(), 1);
+        assert_eq!(lazy_queue.stats().observers_notified, 2);
     }
 
-    implement_self_referencing!(Subject, me);
-    implement_signal_subject!(Subject, subs);
+    struct Callback(Rc<Cell<u32>>);
 
-    struct EventA;
-    implement_event!(EventA, "event_a");
+    impl Executable for Callback {
+        fn execute(&self) -> usize {
+            self.0.set(self.0.get() + 1);
+            0
+        }
+    }
 
-    struct EventB;
-    implement_event!(EventB, "event_b");
+    #[test]
+    fn eventless_executable_queue_test() {
+        let calls = Rc::new(Cell::new(0));
+        let queue = SignalQueue::new();
+        queue.push(Callback(calls.clone()));
+        queue.push(Callback(calls.clone()));
 
-    struct EventC;
-    implement_event!(EventC, "event_c");
+        assert!(queue.histogram().is_empty());
+        assert!(format!("{:?}", queue).contains("events: [<no event>, <no event>]"));
+        assert_eq!(queue.process_all(), 2);
+        assert_eq!(calls.get(), 2);
+    }
 
     #[test]
-    fn add_and_signal_observer() {
+    fn insert_observer_at_test() {
         let subject = Subject::new();
-        let observer = Observer::new();
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let first: Rc<RefCell<dyn SignalObserver>> = OrderObserver::new(1, &log);
+        let second: Rc<RefCell<dyn SignalObserver>> = OrderObserver::new(2, &log);
+        subject.borrow_mut().subscribe_observer(first.clone());
+        subject.borrow_mut().subscribe_observer(second.clone());
 
-        subject.borrow_mut().subscribe_observer(observer.clone());
+        subject.borrow_mut().insert_observer_at(0, OrderObserver::new(3, &log));
+        subject.borrow_mut().insert_observer_at(10, OrderObserver::new(4, &log));
+        subject.borrow_mut().insert_observer_at(1, second.clone());
         subject.borrow().send_signal(Rc::new(EventA{}));
-        subject.borrow().send_signal(Rc::new(EventB{}));
+        assert_eq!(*log.borrow(), vec![3, 2, 1, 4]);
+
+        log.borrow_mut().clear();
+        assert!(subject.borrow_mut().insert_observer_after(&first, OrderObserver::new(5, &log)));
+        assert!(subject.borrow_mut().insert_observer_after(&first, second.clone()));
+        let stranger: Rc<RefCell<dyn SignalObserver>> = OrderObserver::new(6, &log);
+        assert!(!subject.borrow_mut().insert_observer_after(&stranger, second.clone()));
+        subject.borrow().send_signal(Rc::new(EventA{}));
+        assert_eq!(*log.borrow(), vec![3, 1, 2, 5, 4]);
     }
 
     #[test]
-    fn queue_test() {
+    fn weak_signal_snapshot_test() {
+        let subject = Subject::new();
+        let kept = CountingObserver::new();
+        subject.borrow_mut().subscribe_observer(kept.clone());
+        subject.borrow_mut().subscribe_observer(CountingObserver::new());
+
+        let strong = subject.borrow().get_signal_snapshot(Rc::new(EventA{}));
+        let weak = subject.borrow().get_weak_signal_snapshot(Rc::new(EventA{}));
+        subject.borrow_mut().clear_observers();
+        assert_eq!(weak.dead_count(), 0);
+
+        drop(strong);
+        assert_eq!(weak.dead_count(), 1);
+        assert_eq!(weak.execute(), 1);
+        assert_eq!(kept.borrow().received, 1);
+
         let queue = SignalQueue::new();
+        queue.push(weak);
+        drop(kept);
+        assert_eq!(queue.process_all(), 1);
+        assert_eq!(queue.stats().observers_notified, 0);
+    }
+
+    struct DetachingObserver {
+        log: Rc<RefCell<Vec<u32>>>,
+        done: bool
+    }
+
+    impl SignalObserver for DetachingObserver {
+        fn process_signal(&mut self, _event: Rc<dyn Event>) {
+            self.done = true;
+            self.log.borrow_mut().push(1);
+        }
+
+        fn is_expired(&self) -> bool {
+            self.done
+        }
+    }
+
+    #[test]
+    fn self_detaching_observer_test() {
         let subject = Subject::new();
-        let observer = Observer::new();
+        let log = Rc::new(RefCell::new(Vec::new()));
+        subject.borrow_mut().subscribe_observer(Rc::new(RefCell::new(DetachingObserver { log: log.clone(), done: false })));
+        subject.borrow_mut().subscribe_observer(OrderObserver::new(2, &log));
 
-        subject.borrow_mut().subscribe_observer(observer.clone());
+        let counts = Rc::new(RefCell::new(Vec::new()));
+        let seen = counts.clone();
+        let reader = Rc::downgrade(&subject);
+        subject.borrow_mut().subscribe_observer(ClosureObserver::new(move |_event| {
+            seen.borrow_mut().push(reader.upgrade().unwrap().borrow().observer_count());
+        }));
 
-        queue.push(subject.borrow().get_signal_snapshot(Rc::new(EventA{})));
-        queue.next_signal();
+        assert_eq!(subject.send_signal_and_prune(Rc::new(EventA{})), 3);
+        assert_eq!(*log.borrow(), vec![1, 2]);
+        assert_eq!(*counts.borrow(), vec![3]);
+        assert_eq!(subject.borrow().observer_count(), 2);
 
-        queue.push(subject.borrow().get_signal_snapshot(Rc::new(EventB{})));
-        queue.push(subject.borrow().get_signal_snapshot(Rc::new(EventA{})));
-        queue.next_signal();
-        queue.push(subject.borrow().get_signal_snapshot(Rc::new(EventB{})));
-        queue.next_signal();
-        queue.next_signal();
+        subject.borrow().send_signal(Rc::new(EventA{}));
+        assert_eq!(*log.borrow(), vec![1, 2, 2]);
+        assert_eq!(*counts.borrow(), vec![3, 2]);
     }
 
     #[test]
-    fn targeted_signal_test() {
-        let queue = SignalQueue::new();
+    fn queue_with_capacity_test() {
+        #[derive(Default)]
+        struct Component {
+            queue: SignalQueue
+        }
+
+        let component = Component::default();
+        assert!(component.queue.is_empty());
+
+        let queue = SignalQueue::with_capacity(4);
         let subject = Subject::new();
-        let observer_target = Observer::new();
-        let observer_background = ObserverBackground::new();
+        for _ in 0..6 {
+            assert!(queue.push(subject.borrow().get_signal_snapshot(Rc::new(EventA{}))).is_none());
+        }
+        assert_eq!(queue.len(), 6);
+        assert_eq!(queue.process_all(), 6);
+    }
 
-        subject.borrow_mut().subscribe_observer(observer_background.clone());
+    #[test]
+    fn subscribe_observer_allow_duplicates_test() {
+        let subject = Subject::new();
+        let counter = CountingObserver::new();
+        let counter_dyn: Rc<RefCell<dyn SignalObserver>> = counter.clone();
+        subject.borrow_mut().subscribe_observer(counter.clone());
+        subject.borrow_mut().subscribe_observer_allow_duplicates(counter.clone());
+        assert_eq!(subject.borrow().observer_count(), 2);
 
         subject.borrow().send_signal(Rc::new(EventA{}));
-        
-        subject.borrow().send_signal_to(Rc::new(EventB{}), &vec![observer_target.clone()]);
+        assert_eq!(counter.borrow().received, 2);
 
-        queue.push(subject.borrow().get_signal_to_snapshot(Rc::new(EventC{}), &vec![observer_target.clone()]));
-        queue.next_signal();
+        assert!(subject.borrow_mut().unsubscribe_observer(&counter_dyn));
+        assert!(subject.borrow().is_subscribed(&counter_dyn));
+        subject.borrow().send_signal(Rc::new(EventA{}));
+        assert_eq!(counter.borrow().received, 3);
+
+        assert!(subject.borrow_mut().unsubscribe_observer(&counter_dyn));
+        assert!(!subject.borrow().is_subscribed(&counter_dyn));
+    }
+
+    #[test]
+    fn drain_observers_test() {
+        let source = Subject::new();
+        let destination = Subject::new();
+        let log = Rc::new(RefCell::new(Vec::new()));
+        source.borrow_mut().subscribe_observer(OrderObserver::new(1, &log));
+        source.borrow_mut().subscribe_observer(OrderObserver::new(2, &log));
+        destination.borrow_mut().subscribe_observer(OrderObserver::new(3, &log));
+
+        let drained = source.borrow_mut().drain_observers();
+        assert_eq!(drained.len(), 2);
+        assert!(!source.borrow().has_observers());
+
+        destination.borrow_mut().subscribe_many(drained);
+        destination.borrow().send_signal(Rc::new(EventA{}));
+        assert_eq!(*log.borrow(), vec![3, 1, 2]);
     }
 }
@@ -0,0 +1,712 @@
+use alloc::rc::{Rc, Weak};
+use alloc::borrow::ToOwned;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::cell::{Cell, RefCell};
+use core::ops::ControlFlow;
+use core::any::TypeId;
+#[cfg(feature = "std")]
+use std::sync::mpsc::{self, Receiver, Sender};
+use easy_events::Event;
+use crate::{SignalObserver, SignalSubject, SignalSubjectExt, SignalError, try_process};
+
+/// Implements `SignalObserver` for an adapter around a single observer,
+/// forwarding every method to it, so a new trait method only needs adding
+/// here. `target` gives the wrapped observer, or `None` once it is gone. The
+/// optional clauses adjust the forwarding:
+///
+/// - `deliver_if` is checked before each signal is passed on, and may update
+///   the adapter, e.g. to count deliveries.
+/// - `accepts_if` must hold as well as the wrapped observer's `accepts` for
+///   an event to be accepted.
+/// - `expired_if` expires the adapter even while the wrapped observer has
+///   not expired.
+/// - `priority` replaces the wrapped observer's priority.
+/// - A trailing block holds any other methods the adapter overrides.
+///
+/// `tag` is the one method left alone: it returns a borrow, which cannot
+/// outlive the wrapped observer's `RefCell` guard. Group lookups find a
+/// wrapped tag anyway, by following `wrapped_observer`.
+macro_rules! forward_observer {
+    (@or [] [$($default:tt)*]) => { $($default)* };
+    (@or [$($given:tt)+] [$($default:tt)*]) => { $($given)+ };
+    (
+        $type:ty,
+        target: |$target_this:ident| $target:expr
+        $(, deliver_if: |$deliver_this:ident, $deliver_event:ident| $deliver:expr)?
+        $(, accepts_if: |$accepts_this:ident, $accepts_event:ident| $accepts:expr)?
+        $(, expired_if: |$expired_this:ident| $expired:expr)?
+        $(, priority: |$priority_this:ident| $priority:expr)?
+        $(, { $($extra:tt)* })?
+        $(,)?
+    ) => {
+        impl $type {
+            fn forward_target(&self) -> Option<Rc<RefCell<dyn SignalObserver>>> {
+                let $target_this = self;
+                $target
+            }
+
+            fn forward_gate(&mut self, _event: &Rc<dyn Event>) -> bool {
+                $(
+                    let $deliver_this = &mut *self;
+                    let $deliver_event = _event;
+                    if !$deliver {
+                        return false;
+                    }
+                )?
+                true
+            }
+        }
+
+        impl SignalObserver for $type {
+            fn process_signal(&mut self, event: Rc<dyn Event>) {
+                if self.forward_gate(&event) {
+                    if let Some(o) = self.forward_target() {
+                        o.borrow_mut().process_signal(event);
+                    }
+                }
+            }
+
+            fn try_process_signal(&mut self, event: Rc<dyn Event>) -> Result<(), SignalError> {
+                if !self.forward_gate(&event) {
+                    return Ok(());
+                }
+                match self.forward_target() {
+                    Some(o) => try_process(&o, event),
+                    None => Ok(())
+                }
+            }
+
+            fn process_signal_interruptible(&mut self, event: Rc<dyn Event>) -> ControlFlow<()> {
+                if !self.forward_gate(&event) {
+                    return ControlFlow::Continue(());
+                }
+                match self.forward_target() {
+                    Some(o) => o.borrow_mut().process_signal_interruptible(event),
+                    None => ControlFlow::Continue(())
+                }
+            }
+
+            fn process_signal_from(&mut self, event: Rc<dyn Event>, source: Rc<RefCell<dyn SignalSubject>>) {
+                if self.forward_gate(&event) {
+                    if let Some(o) = self.forward_target() {
+                        o.borrow_mut().process_signal_from(event, source);
+                    }
+                }
+            }
+
+            fn on_subscribe(&mut self, subject: &Rc<RefCell<dyn SignalSubject>>) {
+                if let Some(o) = self.forward_target() {
+                    if let Ok(mut o) = o.try_borrow_mut() {
+                        o.on_subscribe(subject);
+                    }
+                }
+            }
+
+            fn on_unsubscribe(&mut self) {
+                if let Some(o) = self.forward_target() {
+                    if let Ok(mut o) = o.try_borrow_mut() {
+                        o.on_unsubscribe();
+                    }
+                }
+            }
+
+            fn wrapped_observer(&self) -> Option<Rc<RefCell<dyn SignalObserver>>> {
+                self.forward_target()
+            }
+
+            fn priority(&self) -> i32 {
+                forward_observer!(@or [$({
+                    let $priority_this = self;
+                    $priority
+                })?] [
+                    self.forward_target().map_or(0, |o| o.try_borrow().map_or(0, |o| o.priority()))
+                ])
+            }
+
+            fn is_expired(&self) -> bool {
+                $(({
+                    let $expired_this = self;
+                    $expired
+                }) ||)? self.forward_target().map_or(true, |o| o.try_borrow().is_ok_and(|o| o.is_expired()))
+            }
+
+            fn accepts(&self, event: &dyn Event) -> bool {
+                $(({
+                    let $accepts_this = self;
+                    let $accepts_event = event;
+                    $accepts
+                }) &&)? self.forward_target().is_some_and(|o| o.try_borrow().map_or(true, |o| o.accepts(event)))
+            }
+
+            $($($extra)*)?
+        }
+    }
+}
+
+/// Adapts a closure into an observer. The closure is `FnMut`, so it can
+/// mutate what it captures, although state that must be read back afterwards
+/// still needs shared ownership:
+///
+/// ```
+/// # use std::any::{Any, TypeId};
+/// # use std::rc::Rc;
+/// # use std::cell::RefCell;
+/// # use easy_events::{Event, implement_event};
+/// # use easy_signals::{ClosureObserver, EventBus, SignalSubject};
+/// struct Jump;
+/// implement_event!(Jump, "jump");
+///
+/// let seen = Rc::new(RefCell::new(Vec::new()));
+/// let log = seen.clone();
+/// let mut calls = 0;
+/// let observer = ClosureObserver::new(move |event: Rc<dyn Event>| {
+///     calls += 1;
+///     log.borrow_mut().push((calls, event.as_any().type_id()));
+/// });
+///
+/// let bus = EventBus::new();
+/// bus.borrow_mut().subscribe_observer(observer);
+/// bus.borrow().send_signal(Rc::new(Jump));
+/// bus.borrow().send_signal(Rc::new(Jump));
+/// assert_eq!(*seen.borrow(), vec![(1, TypeId::of::<Jump>()), (2, TypeId::of::<Jump>())]);
+/// ```
+pub struct ClosureObserver<F: FnMut(Rc<dyn Event>)> {
+    callback: F
+}
+
+impl<F: FnMut(Rc<dyn Event>)> ClosureObserver<F> {
+    pub fn new(callback: F) -> Rc<RefCell<Self>> {
+        Rc::new(RefCell::new(Self { callback }))
+    }
+}
+
+impl<F: FnMut(Rc<dyn Event>)> SignalObserver for ClosureObserver<F> {
+    fn process_signal(&mut self, event: Rc<dyn Event>) {
+        (self.callback)(event);
+    }
+}
+
+/// Sends every signal it receives into an `mpsc` channel, so that code
+/// written against a `Receiver` can consume them. Events are `Rc`s, so the
+/// channel can only be used on the thread that owns the subject; see the
+/// `sync` module for observers that cross threads.
+#[cfg(feature = "std")]
+pub struct ChannelObserver {
+    sender: Sender<Rc<dyn Event>>
+}
+
+#[cfg(feature = "std")]
+impl ChannelObserver {
+    pub fn new() -> (Rc<RefCell<Self>>, Receiver<Rc<dyn Event>>) {
+        let (sender, receiver) = mpsc::channel();
+        (Rc::new(RefCell::new(Self { sender })), receiver)
+    }
+}
+
+#[cfg(feature = "std")]
+impl SignalObserver for ChannelObserver {
+    fn process_signal(&mut self, event: Rc<dyn Event>) {
+        let _ = self.sender.send(event);
+    }
+
+    /// Fails once the `Receiver` has been dropped.
+    fn try_process_signal(&mut self, event: Rc<dyn Event>) -> Result<(), SignalError> {
+        self.sender.send(event).map_err(|_| "channel receiver was dropped".into())
+    }
+}
+
+/// Re-broadcasts every signal it receives to the observers of another
+/// subject, so subjects can be arranged in trees that signals propagate down.
+///
+/// Forwarding is synchronous. If the target subject can reach back to this
+/// observer, directly or through further forwarding, the event comes back
+/// around to a subject that is still sending it, and `send_signal` stops it
+/// there, so a loop such as A to B to A terminates. Without `std` there is no
+/// such check and a cycle recurses until the stack overflows. Cycles that
+/// send a new event each time around are not caught either;
+/// `hooks::set_max_dispatch_depth` bounds those, or push the signals onto a
+/// `SignalQueue` and drain it with `process_all_bounded`.
+///
+/// The target is dispatched to through `SignalSubjectExt::emit`, so it is not
+/// borrowed while its observers run and they may subscribe to or unsubscribe
+/// from it.
+pub struct ForwardingObserver {
+    subject: Rc<RefCell<dyn SignalSubject>>
+}
+
+impl ForwardingObserver {
+    pub fn new(subject: Rc<RefCell<dyn SignalSubject>>) -> Rc<RefCell<Self>> {
+        Rc::new(RefCell::new(Self { subject }))
+    }
+}
+
+impl SignalObserver for ForwardingObserver {
+    fn process_signal(&mut self, event: Rc<dyn Event>) {
+        self.subject.emit(event);
+    }
+}
+
+/// Like `ForwardingObserver`, but re-broadcasts every signal to several
+/// subjects, in the order they were added. This is the dual of
+/// `SignalSubject::send_signal_to`: one signal fans out into independent
+/// subsystems, each with its own observers.
+///
+/// The caveats of `ForwardingObserver` apply to each target. A target that
+/// can reach back to this observer sends the event back around, where
+/// `send_signal` stops it. Observers of a target may change that target, but
+/// this observer is itself borrowed throughout, so targets cannot be added or
+/// removed from within a forwarded signal.
+pub struct MultiplexObserver {
+    targets: Vec<Rc<RefCell<dyn SignalSubject>>>
+}
+
+impl MultiplexObserver {
+    pub fn new() -> Rc<RefCell<Self>> {
+        Rc::new(RefCell::new(Self { targets: Vec::new() }))
+    }
+
+    /// Adds a subject to forward to. Adding the same subject again keeps its
+    /// original position.
+    pub fn add_target(&mut self, target: Rc<RefCell<dyn SignalSubject>>) {
+        if !self.targets.iter().any(|x| same_subject(x, &target)) {
+            self.targets.push(target);
+        }
+    }
+
+    /// Stops forwarding to `target`, returning whether it was a target.
+    pub fn remove_target(&mut self, target: &Rc<RefCell<dyn SignalSubject>>) -> bool {
+        let count = self.targets.len();
+        self.targets.retain(|x| !same_subject(x, target));
+        count != self.targets.len()
+    }
+
+    pub fn target_count(&self) -> usize {
+        self.targets.len()
+    }
+}
+
+impl SignalObserver for MultiplexObserver {
+    fn process_signal(&mut self, event: Rc<dyn Event>) {
+        for target in self.targets.iter() {
+            target.emit(event.clone());
+        }
+    }
+}
+
+fn same_subject(a: &Rc<RefCell<dyn SignalSubject>>, b: &Rc<RefCell<dyn SignalSubject>>) -> bool {
+    Rc::as_ptr(a) as *const () == Rc::as_ptr(b) as *const ()
+}
+
+/// Gives another observer a fixed dispatch priority. Subscribing through
+/// `SignalSubject::subscribe_observer_with_priority` wraps the observer in
+/// one of these.
+pub struct PrioritizedObserver {
+    observer: Rc<RefCell<dyn SignalObserver>>,
+    priority: i32
+}
+
+impl PrioritizedObserver {
+    pub fn new(observer: Rc<RefCell<dyn SignalObserver>>, priority: i32) -> Rc<RefCell<Self>> {
+        Rc::new(RefCell::new(Self { observer, priority }))
+    }
+}
+
+forward_observer!(
+    PrioritizedObserver,
+    target: |this| Some(this.observer.clone()),
+    priority: |this| this.priority
+);
+
+/// Forwards to an observer held by `Weak`, so subscribing it does not keep
+/// the observer alive. Expires once the observer is dropped.
+pub struct WeakObserver {
+    observer: Weak<RefCell<dyn SignalObserver>>
+}
+
+impl WeakObserver {
+    pub fn new(observer: Weak<RefCell<dyn SignalObserver>>) -> Rc<RefCell<Self>> {
+        Rc::new(RefCell::new(Self { observer }))
+    }
+}
+
+forward_observer!(WeakObserver, target: |this| this.observer.upgrade());
+
+/// Forwards only the first signal it receives to another observer, then
+/// expires so subjects stop dispatching to it and prune it.
+pub struct OnceObserver {
+    observer: Rc<RefCell<dyn SignalObserver>>,
+    fired: bool
+}
+
+impl OnceObserver {
+    pub fn new(observer: Rc<RefCell<dyn SignalObserver>>) -> Rc<RefCell<Self>> {
+        Rc::new(RefCell::new(Self { observer, fired: false }))
+    }
+}
+
+forward_observer!(
+    OnceObserver,
+    target: |this| Some(this.observer.clone()),
+    deliver_if: |this, _event| !core::mem::replace(&mut this.fired, true),
+    expired_if: |this| this.fired
+);
+
+/// Forwards the first `limit` signals it receives to another observer, then
+/// expires like `OnceObserver`.
+pub struct CountedObserver {
+    observer: Rc<RefCell<dyn SignalObserver>>,
+    remaining: usize
+}
+
+impl CountedObserver {
+    pub fn new(observer: Rc<RefCell<dyn SignalObserver>>, limit: usize) -> Rc<RefCell<Self>> {
+        Rc::new(RefCell::new(Self { observer, remaining: limit }))
+    }
+
+    /// How many more signals will be forwarded.
+    pub fn remaining(&self) -> usize {
+        self.remaining
+    }
+
+    fn take_delivery(&mut self) -> bool {
+        if self.remaining == 0 {
+            return false;
+        }
+        self.remaining -= 1;
+        true
+    }
+}
+
+forward_observer!(
+    CountedObserver,
+    target: |this| Some(this.observer.clone()),
+    deliver_if: |this, _event| this.take_delivery(),
+    expired_if: |this| this.remaining == 0
+);
+
+/// Toggles a `GatedObserver` on and off.
+#[derive(Clone, Debug)]
+pub struct GateHandle {
+    enabled: Rc<Cell<bool>>
+}
+
+impl GateHandle {
+    pub fn enable(&self) {
+        self.enabled.set(true);
+    }
+
+    pub fn disable(&self) {
+        self.enabled.set(false);
+    }
+
+    pub fn set_enabled(&self, enabled: bool) {
+        self.enabled.set(enabled);
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled.get()
+    }
+}
+
+/// Forwards to another observer only while its `GateHandle` is enabled. A
+/// disabled observer stays subscribed, in the same position, but is skipped
+/// during dispatch.
+pub struct GatedObserver {
+    observer: Rc<RefCell<dyn SignalObserver>>,
+    enabled: Rc<Cell<bool>>
+}
+
+impl GatedObserver {
+    /// Creates an enabled gate around `observer`.
+    pub fn new(observer: Rc<RefCell<dyn SignalObserver>>) -> (Rc<RefCell<Self>>, GateHandle) {
+        let enabled = Rc::new(Cell::new(true));
+        let handle = GateHandle { enabled: enabled.clone() };
+        (Rc::new(RefCell::new(Self { observer, enabled })), handle)
+    }
+}
+
+forward_observer!(
+    GatedObserver,
+    target: |this| Some(this.observer.clone()),
+    deliver_if: |this, _event| this.enabled.get(),
+    accepts_if: |this, _event| this.enabled.get()
+);
+
+/// Labels another observer with a group tag, so it can be addressed along
+/// with the rest of its group through `SignalSubject::send_signal_to_group`
+/// and `unsubscribe_group`. It reports the wrapped observer's priority, so
+/// tagging an observer does not change where it is dispatched.
+pub struct TaggedObserver {
+    observer: Rc<RefCell<dyn SignalObserver>>,
+    tag: String
+}
+
+impl TaggedObserver {
+    pub fn new(observer: Rc<RefCell<dyn SignalObserver>>, tag: &str) -> Rc<RefCell<Self>> {
+        Rc::new(RefCell::new(Self { observer, tag: tag.to_owned() }))
+    }
+}
+
+forward_observer!(TaggedObserver, target: |this| Some(this.observer.clone()), {
+    fn tag(&self) -> Option<&str> {
+        Some(&self.tag)
+    }
+});
+
+/// The event types a `FilteredObserver` forwards.
+pub type EventTypeSet = alloc::collections::BTreeSet<TypeId>;
+
+/// Forwards to another observer only the events whose concrete type is in a
+/// fixed set.
+pub struct FilteredObserver {
+    observer: Rc<RefCell<dyn SignalObserver>>,
+    event_types: EventTypeSet
+}
+
+impl FilteredObserver {
+    pub fn new(observer: Rc<RefCell<dyn SignalObserver>>, event_types: EventTypeSet) -> Rc<RefCell<Self>> {
+        Rc::new(RefCell::new(Self { observer, event_types }))
+    }
+}
+
+forward_observer!(
+    FilteredObserver,
+    target: |this| Some(this.observer.clone()),
+    deliver_if: |this, event| this.accepts(event.as_ref()),
+    accepts_if: |this, event| this.event_types.contains(&event.as_any().type_id())
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::any::Any;
+    use easy_events::implement_event;
+    use crate::{SignalSubject, implement_signal_subject};
+
+    #[derive(Default)]
+    struct Subject {
+        subs: Vec<Rc<RefCell<dyn SignalObserver>>>
+    }
+
+    implement_signal_subject!(Subject, subs);
+
+    struct EventA;
+    implement_event!(EventA, "event_a");
+
+    #[test]
+    fn closure_observer_test() {
+        let mut subject = Subject::default();
+        let received = Rc::new(Cell::new(0));
+
+        let counter = received.clone();
+        subject.subscribe_observer(ClosureObserver::new(move |_event| {
+            counter.set(counter.get() + 1);
+        }));
+
+        subject.send_signal(Rc::new(EventA{}));
+        subject.send_signal(Rc::new(EventA{}));
+        assert_eq!(received.get(), 2);
+    }
+
+    #[test]
+    fn once_observer_test() {
+        let mut subject = Subject::default();
+        let received = Rc::new(Cell::new(0));
+
+        let counter = received.clone();
+        subject.subscribe_once(ClosureObserver::new(move |_event| {
+            counter.set(counter.get() + 1);
+        }));
+
+        assert_eq!(subject.send_signal(Rc::new(EventA{})), 1);
+        assert_eq!(subject.send_signal(Rc::new(EventA{})), 0);
+        assert_eq!(received.get(), 1);
+        assert_eq!(subject.prune_dead_observers(), 1);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn channel_observer_test() {
+        let mut subject = Subject::default();
+        let (observer, receiver) = ChannelObserver::new();
+        subject.subscribe_observer(observer);
+
+        subject.send_signal(Rc::new(EventA{}));
+        subject.send_signal(Rc::new(EventA{}));
+        assert_eq!(receiver.try_iter().count(), 2);
+
+        drop(receiver);
+        assert_eq!(subject.try_send_signal(Rc::new(EventA{})).len(), 1);
+    }
+
+    #[test]
+    fn forwarding_observer_test() {
+        let mut parent = Subject::default();
+        let child = Rc::new(RefCell::new(Subject::default()));
+        let received = Rc::new(Cell::new(0));
+
+        let counter = received.clone();
+        child.borrow_mut().subscribe_observer(ClosureObserver::new(move |_event| {
+            counter.set(counter.get() + 1);
+        }));
+        parent.subscribe_observer(ForwardingObserver::new(child.clone()));
+
+        assert_eq!(parent.send_signal(Rc::new(EventA{})), 1);
+        assert_eq!(received.get(), 1);
+    }
+
+    #[test]
+    fn forwarding_observer_mutating_target_test() {
+        let mut parent = Subject::default();
+        let child: Rc<RefCell<dyn SignalSubject>> = Rc::new(RefCell::new(Subject::default()));
+
+        let weak_child = Rc::downgrade(&child);
+        child.borrow_mut().subscribe_fn(Box::new(move |_event| {
+            if let Some(child) = weak_child.upgrade() {
+                child.borrow_mut().subscribe_observer(ClosureObserver::new(|_event| {}));
+            }
+        }));
+        parent.subscribe_observer(ForwardingObserver::new(child.clone()));
+
+        parent.send_signal(Rc::new(EventA{}));
+        assert_eq!(child.borrow().observer_count(), 2);
+    }
+
+    #[test]
+    fn multiplex_observer_test() {
+        let mut hub = Subject::default();
+        let received = Rc::new(RefCell::new(Vec::new()));
+        let mut targets: Vec<Rc<RefCell<dyn SignalSubject>>> = Vec::new();
+        for id in 0..2 {
+            let target = Rc::new(RefCell::new(Subject::default()));
+            let log = received.clone();
+            target.borrow_mut().subscribe_observer(ClosureObserver::new(move |_event| {
+                log.borrow_mut().push(id);
+            }));
+            targets.push(target);
+        }
+
+        let multiplex = MultiplexObserver::new();
+        multiplex.borrow_mut().add_target(targets[0].clone());
+        multiplex.borrow_mut().add_target(targets[1].clone());
+        multiplex.borrow_mut().add_target(targets[0].clone());
+        assert_eq!(multiplex.borrow().target_count(), 2);
+        hub.subscribe_observer(multiplex.clone());
+
+        assert_eq!(hub.send_signal(Rc::new(EventA{})), 1);
+        assert_eq!(*received.borrow(), vec![0, 1]);
+
+        assert!(multiplex.borrow_mut().remove_target(&targets[0]));
+        assert!(!multiplex.borrow_mut().remove_target(&targets[0]));
+        hub.send_signal(Rc::new(EventA{}));
+        assert_eq!(*received.borrow(), vec![0, 1, 1]);
+    }
+
+    #[test]
+    fn multiplex_observer_mutating_target_test() {
+        let mut hub = Subject::default();
+        let multiplex = MultiplexObserver::new();
+        let mut targets: Vec<Rc<RefCell<dyn SignalSubject>>> = Vec::new();
+        for _ in 0..2 {
+            let target: Rc<RefCell<dyn SignalSubject>> = Rc::new(RefCell::new(Subject::default()));
+            let weak_target = Rc::downgrade(&target);
+            target.borrow_mut().subscribe_fn(Box::new(move |_event| {
+                if let Some(target) = weak_target.upgrade() {
+                    target.borrow_mut().clear_observers();
+                }
+            }));
+            multiplex.borrow_mut().add_target(target.clone());
+            targets.push(target);
+        }
+        hub.subscribe_observer(multiplex);
+
+        hub.send_signal(Rc::new(EventA{}));
+        assert!(targets.iter().all(|t| !t.borrow().has_observers()));
+    }
+
+    #[test]
+    fn counted_observer_test() {
+        let mut subject = Subject::default();
+        let received = Rc::new(Cell::new(0));
+
+        let counter = received.clone();
+        subject.subscribe_for_count(ClosureObserver::new(move |_event| {
+            counter.set(counter.get() + 1);
+        }), 3);
+
+        for _ in 0..5 {
+            subject.send_signal(Rc::new(EventA{}));
+        }
+        assert_eq!(received.get(), 3);
+        assert_eq!(subject.prune_dead_observers(), 1);
+    }
+
+    #[test]
+    fn gated_observer_test() {
+        let mut subject = Subject::default();
+        let received = Rc::new(Cell::new(0));
+
+        let counter = received.clone();
+        let gate = subject.subscribe_gated(ClosureObserver::new(move |_event| {
+            counter.set(counter.get() + 1);
+        }));
+
+        assert_eq!(subject.send_signal(Rc::new(EventA{})), 1);
+        gate.disable();
+        assert_eq!(subject.send_signal(Rc::new(EventA{})), 0);
+        assert_eq!(subject.observer_count(), 1);
+        gate.enable();
+        assert_eq!(subject.send_signal(Rc::new(EventA{})), 1);
+        assert_eq!(received.get(), 2);
+    }
+
+    fn order_observer(id: u32, log: &Rc<RefCell<Vec<u32>>>) -> Rc<RefCell<dyn SignalObserver>> {
+        let log = log.clone();
+        ClosureObserver::new(move |_event| log.borrow_mut().push(id))
+    }
+
+    #[test]
+    fn wrapped_priority_test() {
+        let log = Rc::new(RefCell::new(Vec::new()));
+        type Wrap = fn(Rc<RefCell<dyn SignalObserver>>) -> Rc<RefCell<dyn SignalObserver>>;
+        let wrappers: [Wrap; 5] = [
+            |o| OnceObserver::new(o),
+            |o| CountedObserver::new(o, 2),
+            |o| GatedObserver::new(o).0,
+            |o| FilteredObserver::new(o, [TypeId::of::<EventA>()].into_iter().collect()),
+            |o| TaggedObserver::new(o, "group")
+        ];
+        for wrap in wrappers {
+            let mut subject = Subject::default();
+            log.borrow_mut().clear();
+            subject.subscribe_observer(order_observer(1, &log));
+            subject.subscribe_observer(wrap(PrioritizedObserver::new(order_observer(2, &log), 5)));
+
+            subject.send_signal(Rc::new(EventA{}));
+            assert_eq!(*log.borrow(), vec![2, 1]);
+        }
+
+        let mut subject = Subject::default();
+        let strong = PrioritizedObserver::new(order_observer(3, &log), 5);
+        let weak: Rc<RefCell<dyn SignalObserver>> = strong.clone();
+        subject.subscribe_observer(order_observer(1, &log));
+        subject.subscribe_weak_observer(Rc::downgrade(&weak));
+        log.borrow_mut().clear();
+        subject.send_signal(Rc::new(EventA{}));
+        assert_eq!(*log.borrow(), vec![3, 1]);
+    }
+
+    #[test]
+    fn wrapped_tag_test() {
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let mut subject = Subject::default();
+        subject.subscribe_observer(order_observer(1, &log));
+        subject.subscribe_once(TaggedObserver::new(order_observer(2, &log), "group"));
+
+        assert_eq!(subject.send_signal_to_group(Rc::new(EventA{}), "group"), 1);
+        assert_eq!(*log.borrow(), vec![2]);
+        assert_eq!(subject.unsubscribe_group("group"), 1);
+        assert_eq!(subject.observer_count(), 1);
+    }
+}
@@ -0,0 +1,24 @@
+//! The items most code using this crate needs, including `Rc`, `RefCell` and
+//! `easy_events::Event`, for a single glob import. Everything here is also
+//! available from its usual path.
+//!
+//! ```
+//! # use std::any::Any;
+//! # use easy_events::implement_event;
+//! use easy_signals::prelude::*;
+//!
+//! struct Ping;
+//! implement_event!(Ping, "ping");
+//!
+//! let bus = EventBus::new();
+//! bus.borrow_mut().subscribe_observer(ClosureObserver::new(|_event| {}));
+//! assert_eq!(bus.borrow().send_signal(Rc::new(Ping)), 1);
+//! ```
+
+pub use alloc::rc::Rc;
+pub use core::cell::RefCell;
+pub use easy_events::Event;
+pub use crate::{
+    ClosureObserver, EventBus, SignalError, SignalObserver, SignalQueue, SignalSnapShot, SignalSubject,
+    SignalSubjectExt, implement_signal_subject
+};
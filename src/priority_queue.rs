@@ -0,0 +1,122 @@
+use core::cell::{Cell, RefCell};
+use core::cmp::{Ordering, Reverse};
+use alloc::collections::BinaryHeap;
+use crate::SignalSnapShot;
+
+struct PrioritizedSignal {
+    priority: i32,
+    seq: Reverse<u64>,
+    signal: SignalSnapShot
+}
+
+impl PartialEq for PrioritizedSignal {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for PrioritizedSignal {}
+
+impl PartialOrd for PrioritizedSignal {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for PrioritizedSignal {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.priority, self.seq).cmp(&(other.priority, other.seq))
+    }
+}
+
+/// A deferred signal queue that executes the highest priority signal first,
+/// and signals of equal priority in the order they were pushed.
+pub struct SignalPriorityQueue {
+    signal_queue: RefCell<BinaryHeap<PrioritizedSignal>>,
+    next_seq: Cell<u64>
+}
+
+impl Default for SignalPriorityQueue {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SignalPriorityQueue {
+    pub fn new() -> Self {
+        Self {
+            signal_queue: RefCell::new(BinaryHeap::new()),
+            next_seq: Cell::new(0)
+        }
+    }
+
+    pub fn push(&self, signal: SignalSnapShot, priority: i32) {
+        let seq = self.next_seq.get();
+        self.next_seq.set(seq + 1);
+        self.signal_queue.borrow_mut().push(PrioritizedSignal {
+            priority,
+            seq: Reverse(seq),
+            signal
+        });
+    }
+
+    fn pop(&self) -> Option<SignalSnapShot> {
+        self.signal_queue.borrow_mut().pop().map(|s| s.signal)
+    }
+
+    pub fn next_signal(&self) -> Option<SignalSnapShot> {
+        if let Some(s) = self.pop() {
+            s.execute();
+            Some(s)
+        } else {
+            None
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.signal_queue.borrow().is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.signal_queue.borrow().len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::any::Any;
+    use std::rc::Rc;
+    use easy_events::{Event, implement_event};
+
+    struct EventA;
+    implement_event!(EventA, "event_a");
+
+    struct EventB;
+    implement_event!(EventB, "event_b");
+
+    struct EventQuit;
+    implement_event!(EventQuit, "event_quit");
+
+    fn snapshot(event: Rc<dyn Event>) -> SignalSnapShot {
+        SignalSnapShot::new(event, Vec::new())
+    }
+
+    #[test]
+    fn priority_order_test() {
+        let queue = SignalPriorityQueue::new();
+
+        queue.push(snapshot(Rc::new(EventA{})), 0);
+        queue.push(snapshot(Rc::new(EventB{})), 0);
+        queue.push(snapshot(Rc::new(EventQuit{})), 100);
+        queue.push(snapshot(Rc::new(EventA{})), -1);
+        assert_eq!(queue.len(), 4);
+
+        assert!(queue.next_signal().unwrap().event().as_any().is::<EventQuit>());
+        assert!(queue.next_signal().unwrap().event().as_any().is::<EventA>());
+        assert!(queue.next_signal().unwrap().event().as_any().is::<EventB>());
+        assert!(queue.next_signal().unwrap().event().as_any().is::<EventA>());
+        assert!(queue.next_signal().is_none());
+        assert!(queue.is_empty());
+    }
+}
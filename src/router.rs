@@ -0,0 +1,93 @@
+use alloc::rc::Rc;
+use alloc::collections::BTreeMap;
+use core::cell::RefCell;
+use core::any::TypeId;
+use easy_events::Event;
+use crate::{EventBus, Origin, SignalObserver, SignalSnapShot, SignalSubject};
+
+/// Keeps a separate observer list per event type, so sending an event only
+/// reaches the observers subscribed to its concrete type. Each list behaves
+/// like a `SignalSubject`, with the same ordering and re-subscription rules.
+#[derive(Default)]
+pub struct SignalRouter {
+    routes: BTreeMap<TypeId, EventBus>
+}
+
+impl SignalRouter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Subscribes an observer to events of type `E`.
+    pub fn subscribe<E: Event + 'static>(&mut self, observer: Rc<RefCell<dyn SignalObserver>>) {
+        self.routes.entry(TypeId::of::<E>()).or_default().subscribe_observer(observer);
+    }
+
+    /// Unsubscribes an observer from events of type `E`, returning whether it
+    /// was subscribed to them.
+    pub fn unsubscribe<E: Event + 'static>(&mut self, observer: &Rc<RefCell<dyn SignalObserver>>) -> bool {
+        let id = TypeId::of::<E>();
+        let removed = self.routes.get_mut(&id).is_some_and(|bus| bus.unsubscribe_observer(observer));
+        if self.routes.get(&id).is_some_and(|bus| !bus.has_observers()) {
+            self.routes.remove(&id);
+        }
+        removed
+    }
+
+    /// Dispatches to the observers subscribed to the event's concrete type,
+    /// returning how many were notified.
+    pub fn send(&self, event: Rc<dyn Event>) -> usize {
+        self.get_signal_snapshot(event).execute()
+    }
+
+    /// Like `SignalSubject::get_signal_snapshot`, for the observers
+    /// subscribed to the event's concrete type.
+    pub fn get_signal_snapshot(&self, event: Rc<dyn Event>) -> SignalSnapShot {
+        let observers = self.routes.get(&event.as_any().type_id())
+            .map(|bus| bus.copy_observers())
+            .unwrap_or_default();
+        SignalSnapShot::from_subject(Origin::of(self), event, observers)
+    }
+
+    /// How many observers are subscribed to events of type `E`.
+    pub fn observer_count<E: Event + 'static>(&self) -> usize {
+        self.routes.get(&TypeId::of::<E>()).map_or(0, |bus| bus.observer_count())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::any::Any;
+    use std::cell::Cell;
+    use easy_events::implement_event;
+    use crate::ClosureObserver;
+
+    struct EventA;
+    implement_event!(EventA, "event_a");
+
+    struct EventB;
+    implement_event!(EventB, "event_b");
+
+    #[test]
+    fn router_test() {
+        let mut router = SignalRouter::new();
+        let received = Rc::new(Cell::new(0));
+
+        let counter = received.clone();
+        let observer: Rc<RefCell<dyn SignalObserver>> = ClosureObserver::new(move |event| {
+            assert!(event.as_any().is::<EventA>());
+            counter.set(counter.get() + 1);
+        });
+        router.subscribe::<EventA>(observer.clone());
+
+        assert_eq!(router.send(Rc::new(EventA{})), 1);
+        assert_eq!(router.send(Rc::new(EventB{})), 0);
+        assert_eq!(received.get(), 1);
+
+        assert!(!router.unsubscribe::<EventB>(&observer));
+        assert!(router.unsubscribe::<EventA>(&observer));
+        assert_eq!(router.observer_count::<EventA>(), 0);
+        assert_eq!(router.send(Rc::new(EventA{})), 0);
+    }
+}
@@ -0,0 +1,235 @@
+use alloc::rc::Rc;
+use alloc::collections::BTreeMap;
+use core::cell::{Cell, RefCell};
+use crate::SignalSnapShot;
+
+struct ScheduledSignal {
+    signal: SignalSnapShot,
+    repeat: Option<(u64, RepeatHandle)>
+}
+
+/// Returned by `ScheduledSignalQueue::push_repeating` to stop the signal from
+/// being rescheduled.
+#[derive(Clone, Debug, Default)]
+pub struct RepeatHandle {
+    cancelled: Rc<Cell<bool>>
+}
+
+impl RepeatHandle {
+    /// Stops the signal from firing again. Safe to call from an observer of
+    /// the signal itself, in which case the current firing still completes.
+    pub fn cancel(&self) {
+        self.cancelled.set(true);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.get()
+    }
+}
+
+/// A deferred signal queue whose signals only execute once a caller-driven
+/// clock reaches the tick they were scheduled for. Ticks are plain `u64`s, so
+/// they can count frames, or milliseconds elapsed since some `Instant`.
+///
+/// Signals due at the same tick execute in the order they were pushed.
+pub struct ScheduledSignalQueue {
+    signal_queue: RefCell<BTreeMap<(u64, u64), ScheduledSignal>>,
+    next_seq: Cell<u64>,
+    now: Cell<u64>
+}
+
+impl Default for ScheduledSignalQueue {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ScheduledSignalQueue {
+    pub fn new() -> Self {
+        Self {
+            signal_queue: RefCell::new(BTreeMap::new()),
+            next_seq: Cell::new(0),
+            now: Cell::new(0)
+        }
+    }
+
+    /// Schedules a signal for tick `when`. A tick that has already been
+    /// reached executes on the next `advance` or `tick`.
+    pub fn push_at(&self, signal: SignalSnapShot, when: u64) {
+        self.schedule(ScheduledSignal { signal, repeat: None }, when);
+    }
+
+    /// Schedules a signal `delay` ticks after the last tick passed to
+    /// `advance` or `tick`.
+    pub fn push_after(&self, signal: SignalSnapShot, delay: u64) {
+        self.push_at(signal, self.now.get().saturating_add(delay));
+    }
+
+    /// Schedules a signal to fire every `every` ticks, starting `every` ticks
+    /// after the current tick, until the returned handle is cancelled. Each
+    /// firing is rescheduled relative to when it was due rather than when it
+    /// ran, so an `advance` that skips ahead fires it once per missed
+    /// interval. A repeat whose next firing would fall after tick `u64::MAX`
+    /// is not rescheduled.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `every` is 0.
+    pub fn push_repeating(&self, signal: SignalSnapShot, every: u64) -> RepeatHandle {
+        assert!(every > 0, "ScheduledSignalQueue::push_repeating called with an interval of 0");
+        let handle = RepeatHandle::default();
+        let when = self.now.get().saturating_add(every);
+        self.schedule(ScheduledSignal { signal, repeat: Some((every, handle.clone())) }, when);
+        handle
+    }
+
+    fn schedule(&self, signal: ScheduledSignal, when: u64) {
+        let seq = self.next_seq.get();
+        self.next_seq.set(seq + 1);
+        self.signal_queue.borrow_mut().insert((when, seq), signal);
+    }
+
+    fn pop_due(&self, now: u64) -> Option<(u64, ScheduledSignal)> {
+        let mut queue = self.signal_queue.borrow_mut();
+        loop {
+            let entry = queue.first_entry()?;
+            if entry.key().0 > now {
+                return None;
+            }
+            let when = entry.key().0;
+            let signal = entry.remove();
+            if !signal.repeat.as_ref().is_some_and(|(_, handle)| handle.is_cancelled()) {
+                return Some((when, signal));
+            }
+        }
+    }
+
+    /// Moves the clock to `now` and executes the next signal due by then, if
+    /// any, returning it.
+    pub fn tick(&self, now: u64) -> Option<SignalSnapShot> {
+        self.now.set(self.now.get().max(now));
+        let (when, s) = self.pop_due(now)?;
+        s.signal.execute();
+        match s.repeat {
+            Some((every, handle)) if !handle.is_cancelled() => match when.checked_add(every) {
+                Some(next) => {
+                    let fired = s.signal.clone();
+                    self.schedule(ScheduledSignal { signal: s.signal, repeat: Some((every, handle)) }, next);
+                    Some(fired)
+                }
+                None => Some(s.signal)
+            },
+            _ => Some(s.signal)
+        }
+    }
+
+    /// Moves the clock to `now` and executes every signal due by then, in
+    /// order, returning how many ran. Signals scheduled while advancing run in
+    /// the same call if they are already due.
+    pub fn advance(&self, now: u64) -> usize {
+        let mut count = 0;
+        while self.tick(now).is_some() {
+            count += 1;
+        }
+        count
+    }
+
+    /// The last tick passed to `advance` or `tick`.
+    pub fn now(&self) -> u64 {
+        self.now.get()
+    }
+
+    /// The tick the next signal is scheduled for.
+    pub fn next_due(&self) -> Option<u64> {
+        self.signal_queue.borrow().keys().next().map(|k| k.0)
+    }
+
+    /// Cancelled repeating signals are counted until they next come due.
+    pub fn is_empty(&self) -> bool {
+        self.signal_queue.borrow().is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.signal_queue.borrow().len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::any::Any;
+    use easy_events::{Event, implement_event};
+    use crate::SignalObserver;
+
+    struct EventA;
+    implement_event!(EventA, "event_a");
+
+    struct EventB;
+    implement_event!(EventB, "event_b");
+
+    fn snapshot(event: Rc<dyn Event>) -> SignalSnapShot {
+        SignalSnapShot::new(event, Vec::new())
+    }
+
+    #[test]
+    fn scheduled_order_test() {
+        let queue = ScheduledSignalQueue::new();
+
+        queue.push_at(snapshot(Rc::new(EventB{})), 10);
+        queue.push_at(snapshot(Rc::new(EventA{})), 5);
+        queue.push_at(snapshot(Rc::new(EventB{})), 5);
+        assert_eq!(queue.next_due(), Some(5));
+
+        assert!(queue.tick(4).is_none());
+        assert!(queue.tick(5).unwrap().event().as_any().is::<EventA>());
+        assert!(queue.tick(5).unwrap().event().as_any().is::<EventB>());
+        assert!(queue.tick(5).is_none());
+
+        queue.push_after(snapshot(Rc::new(EventA{})), 3);
+        assert_eq!(queue.next_due(), Some(8));
+        assert_eq!(queue.advance(10), 2);
+        assert!(queue.is_empty());
+    }
+
+    struct CancellingObserver {
+        handle: Rc<RefCell<Option<RepeatHandle>>>,
+        received: u32
+    }
+
+    impl SignalObserver for CancellingObserver {
+        fn process_signal(&mut self, _event: Rc<dyn Event>) {
+            self.received += 1;
+            if self.received == 2 {
+                self.handle.borrow().as_ref().unwrap().cancel();
+            }
+        }
+    }
+
+    #[test]
+    fn repeating_test() {
+        let queue = ScheduledSignalQueue::new();
+        let handle = Rc::new(RefCell::new(None));
+        let observer = Rc::new(RefCell::new(CancellingObserver { handle: handle.clone(), received: 0 }));
+
+        let repeat = queue.push_repeating(SignalSnapShot::new(Rc::new(EventA{}), vec![observer.clone()]), 4);
+        *handle.borrow_mut() = Some(repeat.clone());
+
+        assert_eq!(queue.advance(3), 0);
+        assert_eq!(queue.advance(4), 1);
+        assert_eq!(queue.len(), 1);
+        assert_eq!(queue.advance(20), 1);
+        assert!(repeat.is_cancelled());
+        assert!(queue.is_empty());
+        assert_eq!(observer.borrow().received, 2);
+    }
+
+    #[test]
+    fn repeating_overflow_test() {
+        let queue = ScheduledSignalQueue::new();
+        let repeat = queue.push_repeating(snapshot(Rc::new(EventA{})), u64::MAX / 2 + 1);
+
+        assert_eq!(queue.advance(u64::MAX), 1);
+        assert!(!repeat.is_cancelled());
+        assert!(queue.is_empty());
+    }
+}
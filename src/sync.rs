@@ -0,0 +1,259 @@
+//! Thread-safe counterparts of the `Rc`/`RefCell` based types, built on
+//! `Arc` and `Mutex`. Dispatch works the same way as in the crate root.
+
+use std::sync::{Arc, Mutex};
+use core::slice::Iter;
+use std::collections::VecDeque;
+use easy_events::Event;
+
+pub trait SyncSignalObserver: Send {
+    fn process_signal(&mut self, event: Arc<dyn Event + Send + Sync>);
+}
+
+pub trait SyncSignalSubject {
+    fn get_observers_iter(&self) -> Iter<'_, Arc<Mutex<dyn SyncSignalObserver>>>;
+    fn get_observers_mut(&mut self) -> &mut Vec<Arc<Mutex<dyn SyncSignalObserver>>>;
+    fn subscribe_observer(&mut self, new_observer: Arc<Mutex<dyn SyncSignalObserver>>) {
+        let observers = self.get_observers_mut();
+        observers.retain(|x| !Arc::ptr_eq(x, &new_observer));
+        observers.push(new_observer);
+    }
+    fn unsubscribe_observer(&mut self, observer: &Arc<Mutex<dyn SyncSignalObserver>>) -> bool {
+        let observers = self.get_observers_mut();
+        if let Some(index) = observers.iter().position(|x| Arc::ptr_eq(x, observer)) {
+            observers.remove(index);
+            true
+        } else {
+            false
+        }
+    }
+    fn observer_count(&self) -> usize {
+        self.get_observers_iter().len()
+    }
+    fn copy_observers(&self) -> Vec<Arc<Mutex<dyn SyncSignalObserver>>> {
+        self.get_observers_iter().cloned().collect()
+    }
+    fn send_signal(&self, event: Arc<dyn Event + Send + Sync>) -> usize {
+        dispatch_signal(&self.copy_observers(), event)
+    }
+    /// Like `send_signal`, but notifies the observers concurrently on the
+    /// `rayon` thread pool and returns once all of them have finished. The
+    /// order observers are notified in is unspecified, and any state they
+    /// share with each other must be synchronized.
+    #[cfg(feature = "rayon")]
+    fn send_signal_parallel(&self, event: Arc<dyn Event + Send + Sync>) -> usize {
+        use rayon::prelude::*;
+        let observers = self.copy_observers();
+        observers.par_iter().for_each(|o| o.lock().unwrap().process_signal(event.clone()));
+        observers.len()
+    }
+    fn get_signal_snapshot(&self, event: Arc<dyn Event + Send + Sync>) -> SyncSignalSnapShot {
+        SyncSignalSnapShot::new(event, self.copy_observers())
+    }
+    fn send_signal_to(&self, event: Arc<dyn Event + Send + Sync>, targets: &[Arc<Mutex<dyn SyncSignalObserver>>]) -> usize {
+        let mut subs = self.copy_observers();
+        append_unique_observers(&mut subs, targets);
+        dispatch_signal(&subs, event)
+    }
+    fn get_signal_to_snapshot(&self, event: Arc<dyn Event + Send + Sync>, targets: &[Arc<Mutex<dyn SyncSignalObserver>>]) -> SyncSignalSnapShot {
+        let mut subs = self.copy_observers();
+        append_unique_observers(&mut subs, targets);
+        SyncSignalSnapShot::new(event, subs)
+    }
+}
+
+fn dispatch_signal(observers: &[Arc<Mutex<dyn SyncSignalObserver>>], event: Arc<dyn Event + Send + Sync>) -> usize {
+    for o in observers.iter() {
+        o.lock().unwrap().process_signal(event.clone());
+    }
+    observers.len()
+}
+
+fn append_unique_observers(subs: &mut Vec<Arc<Mutex<dyn SyncSignalObserver>>>, targets: &[Arc<Mutex<dyn SyncSignalObserver>>]) {
+    for t in targets.iter() {
+        if !subs.iter().any(|x| Arc::ptr_eq(x, t)) {
+            subs.push(t.clone());
+        }
+    }
+}
+
+/// Implements `SyncSignalSubject` for a struct whose `$observers_field` is a
+/// `Vec<Arc<Mutex<dyn SyncSignalObserver>>>`.
+#[macro_export]
+macro_rules! implement_sync_signal_subject {
+    (
+        $struct:ident,
+        $observers_field:ident $(,)?
+    ) => {
+        impl $crate::sync::SyncSignalSubject for $struct {
+            fn get_observers_iter(&self) -> ::core::slice::Iter<'_, ::std::sync::Arc<::std::sync::Mutex<dyn $crate::sync::SyncSignalObserver>>> {
+                self.$observers_field.iter()
+            }
+
+            fn get_observers_mut(&mut self) -> &mut ::std::vec::Vec<::std::sync::Arc<::std::sync::Mutex<dyn $crate::sync::SyncSignalObserver>>> {
+                &mut self.$observers_field
+            }
+        }
+    }
+}
+
+pub struct SyncSignalSnapShot {
+    event: Arc<dyn Event + Send + Sync>,
+    subs: Vec<Arc<Mutex<dyn SyncSignalObserver>>>
+}
+
+implement_sync_signal_subject!(SyncSignalSnapShot, subs);
+
+impl SyncSignalSnapShot {
+    pub fn new(event: Arc<dyn Event + Send + Sync>, subs: Vec<Arc<Mutex<dyn SyncSignalObserver>>>) -> Self {
+        Self {
+            event,
+            subs
+        }
+    }
+
+    pub fn event(&self) -> &Arc<dyn Event + Send + Sync> {
+        &self.event
+    }
+
+    pub fn subscribers(&self) -> &[Arc<Mutex<dyn SyncSignalObserver>>] {
+        &self.subs
+    }
+
+    pub fn execute(&self) -> usize {
+        self.send_signal(self.event.clone())
+    }
+}
+
+/// A FIFO queue of deferred signals that can be shared between threads.
+///
+/// The queue lock is released before a popped signal is executed, so
+/// observers may push new signals while being notified.
+pub struct SyncSignalQueue {
+    signal_queue: Mutex<VecDeque<SyncSignalSnapShot>>
+}
+
+impl Default for SyncSignalQueue {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SyncSignalQueue {
+    pub fn new() -> Self {
+        Self {
+            signal_queue: Mutex::new(VecDeque::new())
+        }
+    }
+
+    pub fn push(&self, signal: SyncSignalSnapShot) {
+        self.signal_queue.lock().unwrap().push_back(signal);
+    }
+
+    fn pop(&self) -> Option<SyncSignalSnapShot> {
+        self.signal_queue.lock().unwrap().pop_front()
+    }
+
+    pub fn next_signal(&self) -> Option<SyncSignalSnapShot> {
+        if let Some(s) = self.pop() {
+            s.execute();
+            Some(s)
+        } else {
+            None
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.signal_queue.lock().unwrap().is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.signal_queue.lock().unwrap().len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::any::Any;
+    use std::thread;
+    use easy_events::implement_event;
+
+    struct CountingObserver {
+        received: usize
+    }
+
+    impl SyncSignalObserver for CountingObserver {
+        fn process_signal(&mut self, _event: Arc<dyn Event + Send + Sync>) {
+            self.received += 1;
+        }
+    }
+
+    #[derive(Default)]
+    struct Subject {
+        subs: Vec<Arc<Mutex<dyn SyncSignalObserver>>>
+    }
+
+    implement_sync_signal_subject!(Subject, subs);
+
+    struct EventA;
+    implement_event!(EventA, "event_a");
+
+    #[test]
+    fn send_across_threads_test() {
+        let subject = Arc::new(Mutex::new(Subject::default()));
+        let counter = Arc::new(Mutex::new(CountingObserver { received: 0 }));
+
+        subject.lock().unwrap().subscribe_observer(counter.clone());
+        subject.lock().unwrap().subscribe_observer(counter.clone());
+
+        let handles: Vec<_> = (0..4).map(|_| {
+            let subject = subject.clone();
+            thread::spawn(move || {
+                subject.lock().unwrap().send_signal(Arc::new(EventA{}))
+            })
+        }).collect();
+
+        for handle in handles {
+            assert_eq!(handle.join().unwrap(), 1);
+        }
+        assert_eq!(counter.lock().unwrap().received, 4);
+    }
+
+    #[test]
+    fn sync_queue_test() {
+        let queue = Arc::new(SyncSignalQueue::new());
+        let mut subject = Subject::default();
+        let counter = Arc::new(Mutex::new(CountingObserver { received: 0 }));
+        let other = Arc::new(Mutex::new(CountingObserver { received: 0 }));
+
+        subject.subscribe_observer(counter.clone());
+        queue.push(subject.get_signal_snapshot(Arc::new(EventA{})));
+        let targets: [Arc<Mutex<dyn SyncSignalObserver>>; 1] = [other.clone()];
+        queue.push(subject.get_signal_to_snapshot(Arc::new(EventA{}), &targets));
+        assert_eq!(queue.len(), 2);
+
+        let worker = {
+            let queue = queue.clone();
+            thread::spawn(move || while queue.next_signal().is_some() {})
+        };
+        worker.join().unwrap();
+
+        assert!(queue.is_empty());
+        assert_eq!(counter.lock().unwrap().received, 2);
+        assert_eq!(other.lock().unwrap().received, 1);
+    }
+
+    #[test]
+    #[cfg(feature = "rayon")]
+    fn send_signal_parallel_test() {
+        let mut subject = Subject::default();
+        let counters: Vec<_> = (0..8).map(|_| Arc::new(Mutex::new(CountingObserver { received: 0 }))).collect();
+        for counter in counters.iter() {
+            subject.subscribe_observer(counter.clone());
+        }
+
+        assert_eq!(subject.send_signal_parallel(Arc::new(EventA{})), 8);
+        assert!(counters.iter().all(|c| c.lock().unwrap().received == 1));
+    }
+}
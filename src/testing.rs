@@ -0,0 +1,108 @@
+//! Observers and assertions for testing code built on this crate. Enabled by
+//! the `testing` feature.
+
+use std::rc::Rc;
+use std::cell::RefCell;
+use std::any::TypeId;
+use easy_events::Event;
+use crate::SignalObserver;
+
+/// Records every event it receives, in order.
+#[derive(Default)]
+pub struct RecordingObserver {
+    events: Vec<Rc<dyn Event>>
+}
+
+impl RecordingObserver {
+    pub fn new() -> Rc<RefCell<Self>> {
+        Rc::new(RefCell::new(Self::default()))
+    }
+
+    pub fn received_count(&self) -> usize {
+        self.events.len()
+    }
+
+    /// Number of received events whose concrete type is `E`.
+    pub fn received<E: Event + 'static>(&self) -> usize {
+        self.events.iter().filter(|e| e.as_any().is::<E>()).count()
+    }
+
+    pub fn received_types(&self) -> Vec<TypeId> {
+        self.events.iter().map(|e| e.as_any().type_id()).collect()
+    }
+
+    pub fn events(&self) -> &[Rc<dyn Event>] {
+        &self.events
+    }
+
+    pub fn last_event(&self) -> Option<Rc<dyn Event>> {
+        self.events.last().cloned()
+    }
+
+    pub fn clear(&mut self) {
+        self.events.clear();
+    }
+}
+
+impl SignalObserver for RecordingObserver {
+    fn process_signal(&mut self, event: Rc<dyn Event>) {
+        self.events.push(event);
+    }
+}
+
+/// Panics unless `observer` has received at least one `E`.
+#[track_caller]
+pub fn assert_received<E: Event + 'static>(observer: &Rc<RefCell<RecordingObserver>>) {
+    assert!(
+        observer.borrow().received::<E>() > 0,
+        "expected observer to have received a {}",
+        std::any::type_name::<E>()
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::any::Any;
+    use easy_events::implement_event;
+    use crate::{SignalSubject, implement_signal_subject};
+
+    #[derive(Default)]
+    struct Subject {
+        subs: Vec<Rc<RefCell<dyn SignalObserver>>>
+    }
+
+    implement_signal_subject!(Subject, subs);
+
+    struct EventA;
+    implement_event!(EventA, "event_a");
+
+    struct EventB;
+    implement_event!(EventB, "event_b");
+
+    #[test]
+    fn recording_observer_test() {
+        let mut subject = Subject::default();
+        let recorder = RecordingObserver::new();
+
+        subject.subscribe_observer(recorder.clone());
+        subject.send_signal(Rc::new(EventA{}));
+        subject.send_signal(Rc::new(EventB{}));
+        subject.send_signal(Rc::new(EventA{}));
+
+        assert_eq!(recorder.borrow().received_count(), 3);
+        assert_eq!(recorder.borrow().received::<EventA>(), 2);
+        assert_eq!(recorder.borrow().received_types()[1], TypeId::of::<EventB>());
+        assert!(recorder.borrow().last_event().unwrap().as_any().is::<EventA>());
+        assert_received::<EventB>(&recorder);
+
+        recorder.borrow_mut().clear();
+        assert_eq!(recorder.borrow().received_count(), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "expected observer to have received")]
+    fn assert_received_panics_test() {
+        assert_received::<EventA>(&RecordingObserver::new());
+    }
+}
@@ -0,0 +1,143 @@
+use alloc::rc::Rc;
+use core::cell::RefCell;
+use core::marker::PhantomData;
+use easy_events::Event;
+use crate::SignalObserver;
+
+pub trait TypedObserver<E: Event> {
+    fn on_event(&mut self, event: &E);
+}
+
+/// Wraps a `TypedObserver<E>` so it can be subscribed like any other
+/// `SignalObserver`. Events that are not an `E` are ignored.
+pub struct TypedObserverAdapter<E: Event, O: TypedObserver<E>> {
+    observer: O,
+    _event: PhantomData<fn(&E)>
+}
+
+impl<E: Event, O: TypedObserver<E>> TypedObserverAdapter<E, O> {
+    pub fn new(observer: O) -> Rc<RefCell<Self>> {
+        Rc::new(RefCell::new(Self {
+            observer,
+            _event: PhantomData
+        }))
+    }
+
+    pub fn get(&self) -> &O {
+        &self.observer
+    }
+
+    pub fn get_mut(&mut self) -> &mut O {
+        &mut self.observer
+    }
+}
+
+impl<E: Event + 'static, O: TypedObserver<E>> SignalObserver for TypedObserverAdapter<E, O> {
+    fn process_signal(&mut self, event: Rc<dyn Event>) {
+        if let Some(e) = event.as_any().downcast_ref::<E>() {
+            self.observer.on_event(e);
+        }
+    }
+}
+
+/// Implements `SignalObserver` for `$struct`, calling `$handler` with each
+/// received event of the listed type.
+#[macro_export]
+macro_rules! implement_typed_observer {
+    (
+        $struct:ident,
+        $($event:ty => $handler:ident),+ $(,)?
+    ) => {
+        impl $crate::SignalObserver for $struct {
+            fn process_signal(&mut self, event: $crate::__private::Rc<dyn $crate::__private::Event>) {
+                $(
+                    if let Some(e) = $crate::__private::Event::as_any(&*event).downcast_ref::<$event>() {
+                        self.$handler(e);
+                    }
+                )+
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::any::Any;
+    use easy_events::implement_event;
+    use crate::{SignalSubject, implement_signal_subject};
+
+    #[derive(Default)]
+    struct Subject {
+        subs: Vec<Rc<RefCell<dyn SignalObserver>>>
+    }
+
+    implement_signal_subject!(Subject, subs);
+
+    struct EventA;
+    implement_event!(EventA, "event_a");
+
+    struct EventB;
+    implement_event!(EventB, "event_b");
+
+    #[derive(Default)]
+    struct Tally {
+        a: usize
+    }
+
+    impl TypedObserver<EventA> for Tally {
+        fn on_event(&mut self, _event: &EventA) {
+            self.a += 1;
+        }
+    }
+
+    // Kept apart from the imports above, to check the macro needs none.
+    mod macro_tally {
+        use super::{EventA, EventB};
+
+        #[derive(Default)]
+        pub(super) struct MacroTally {
+            pub(super) a: usize,
+            pub(super) b: usize
+        }
+
+        impl MacroTally {
+            fn handle_a(&mut self, _event: &EventA) {
+                self.a += 1;
+            }
+
+            fn handle_b(&mut self, _event: &EventB) {
+                self.b += 1;
+            }
+        }
+
+        crate::implement_typed_observer!(MacroTally, EventA => handle_a, EventB => handle_b);
+    }
+    use macro_tally::MacroTally;
+
+    #[test]
+    fn typed_adapter_test() {
+        let mut subject = Subject::default();
+        let adapter = TypedObserverAdapter::<EventA, _>::new(Tally::default());
+
+        subject.subscribe_observer(adapter.clone());
+        subject.send_signal(Rc::new(EventA{}));
+        subject.send_signal(Rc::new(EventB{}));
+
+        assert_eq!(adapter.borrow().get().a, 1);
+    }
+
+    #[test]
+    fn typed_macro_test() {
+        let mut subject = Subject::default();
+        let observer = Rc::new(RefCell::new(MacroTally::default()));
+
+        subject.subscribe_observer(observer.clone());
+        subject.send_signal(Rc::new(EventA{}));
+        subject.send_signal(Rc::new(EventB{}));
+        subject.send_signal(Rc::new(EventB{}));
+
+        assert_eq!(observer.borrow().a, 1);
+        assert_eq!(observer.borrow().b, 2);
+    }
+}
@@ -0,0 +1,136 @@
+use alloc::rc::Rc;
+use alloc::vec::Vec;
+use core::cell::RefCell;
+
+/// An observer of a single concrete event type, usually an enum covering
+/// every event an app sends. Unlike `SignalObserver`, events arrive as `&E`
+/// and need no downcasting. Any `FnMut(&E)` closure is a `TypedSignalObserver`.
+pub trait TypedSignalObserver<E> {
+    fn process_signal(&mut self, event: &E);
+}
+
+impl<E, F: FnMut(&E)> TypedSignalObserver<E> for F {
+    fn process_signal(&mut self, event: &E) {
+        self(event)
+    }
+}
+
+/// A subject for apps with a closed set of events, which sends `&E` to its
+/// `TypedSignalObserver<E>`s instead of going through `dyn Event`. It follows
+/// `SignalSubject` where the two overlap: observers are notified in
+/// subscription order, subscribing the same observer again keeps its
+/// original position, and subscription changes made during a dispatch take
+/// effect from the next one. Dispatch hooks do not run for typed subjects.
+pub struct TypedSubject<E> {
+    observers: Vec<Rc<RefCell<dyn TypedSignalObserver<E>>>>
+}
+
+impl<E> Default for TypedSubject<E> {
+    fn default() -> Self {
+        Self { observers: Vec::new() }
+    }
+}
+
+impl<E> TypedSubject<E> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn subscribe_observer(&mut self, observer: Rc<RefCell<dyn TypedSignalObserver<E>>>) {
+        if !self.is_subscribed(&observer) {
+            self.observers.push(observer);
+        }
+    }
+
+    /// Subscribes a closure, returning a handle that can be passed to
+    /// `unsubscribe_observer`.
+    pub fn subscribe_fn<F: FnMut(&E) + 'static>(&mut self, f: F) -> Rc<RefCell<dyn TypedSignalObserver<E>>>
+    where
+        E: 'static
+    {
+        let observer: Rc<RefCell<dyn TypedSignalObserver<E>>> = Rc::new(RefCell::new(f));
+        self.subscribe_observer(observer.clone());
+        observer
+    }
+
+    /// Removes an observer, returning whether it was subscribed.
+    pub fn unsubscribe_observer(&mut self, observer: &Rc<RefCell<dyn TypedSignalObserver<E>>>) -> bool {
+        let count = self.observers.len();
+        self.observers.retain(|x| !Rc::ptr_eq(x, observer));
+        count != self.observers.len()
+    }
+
+    pub fn is_subscribed(&self, observer: &Rc<RefCell<dyn TypedSignalObserver<E>>>) -> bool {
+        self.observers.iter().any(|x| Rc::ptr_eq(x, observer))
+    }
+
+    pub fn clear_observers(&mut self) {
+        self.observers.clear();
+    }
+
+    pub fn observer_count(&self) -> usize {
+        self.observers.len()
+    }
+
+    /// Sends `event` to every observer, returning how many were notified.
+    pub fn send_signal(&self, event: &E) -> usize {
+        let observers = self.observers.clone();
+        for o in observers.iter() {
+            o.borrow_mut().process_signal(event);
+        }
+        observers.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    enum AppEvent {
+        Add(u32),
+        Reset
+    }
+
+    struct Total {
+        value: u32
+    }
+
+    impl TypedSignalObserver<AppEvent> for Total {
+        fn process_signal(&mut self, event: &AppEvent) {
+            match event {
+                AppEvent::Add(n) => self.value += n,
+                AppEvent::Reset => self.value = 0
+            }
+        }
+    }
+
+    #[test]
+    fn typed_subject_test() {
+        let mut subject = TypedSubject::new();
+        let total = Rc::new(RefCell::new(Total { value: 0 }));
+        let resets = Rc::new(Cell::new(0));
+
+        let counter = resets.clone();
+        let closure = subject.subscribe_fn(move |event| {
+            if let AppEvent::Reset = event {
+                counter.set(counter.get() + 1);
+            }
+        });
+        subject.subscribe_observer(total.clone());
+        subject.subscribe_observer(total.clone());
+        assert_eq!(subject.observer_count(), 2);
+
+        assert_eq!(subject.send_signal(&AppEvent::Add(3)), 2);
+        subject.send_signal(&AppEvent::Add(4));
+        assert_eq!(total.borrow().value, 7);
+
+        subject.send_signal(&AppEvent::Reset);
+        assert_eq!(total.borrow().value, 0);
+        assert_eq!(resets.get(), 1);
+
+        assert!(subject.unsubscribe_observer(&closure));
+        subject.send_signal(&AppEvent::Reset);
+        assert_eq!(resets.get(), 1);
+    }
+}
@@ -0,0 +1,61 @@
+#![cfg(feature = "derive")]
+
+use std::any::Any;
+use std::rc::Rc;
+use std::cell::RefCell;
+use easy_events::{Event, implement_event};
+use easy_signals::{SignalObserver, SignalSubject};
+
+extern crate easy_signals as renamed;
+
+struct EventA;
+implement_event!(EventA, "event_a");
+
+struct CountingObserver {
+    received: usize
+}
+
+impl SignalObserver for CountingObserver {
+    fn process_signal(&mut self, _event: Rc<dyn Event>) {
+        self.received += 1;
+    }
+}
+
+#[derive(SignalSubject)]
+struct Subject {
+    name: &'static str,
+    #[observers]
+    subs: Vec<Rc<RefCell<dyn SignalObserver>>>,
+    /// Callback fields with `->` in their type are skipped over correctly.
+    pub(crate) scores: std::collections::HashMap<u32, Box<dyn Fn(u32, u32) -> u32>>
+}
+
+#[test]
+fn derived_subject_test() {
+    let mut subject = Subject {
+        name: "subject",
+        subs: Vec::new(),
+        scores: Default::default()
+    };
+    let counter = Rc::new(RefCell::new(CountingObserver { received: 0 }));
+
+    subject.subscribe_observer(counter.clone());
+    assert_eq!(subject.send_signal(Rc::new(EventA{})), 1);
+    assert_eq!(counter.borrow().received, 1);
+    assert_eq!(subject.name, "subject");
+    assert!(subject.scores.is_empty());
+}
+
+#[derive(SignalSubject)]
+#[signal_subject(crate = ::renamed)]
+struct RenamedSubject {
+    #[observers]
+    subs: Vec<Rc<RefCell<dyn SignalObserver>>>
+}
+
+#[test]
+fn derived_subject_renamed_crate_test() {
+    let mut subject = RenamedSubject { subs: Vec::new() };
+    subject.subscribe_observer(Rc::new(RefCell::new(CountingObserver { received: 0 })));
+    assert_eq!(subject.send_signal(Rc::new(EventA{})), 1);
+}
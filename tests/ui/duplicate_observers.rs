@@ -0,0 +1,13 @@
+use std::rc::Rc;
+use std::cell::RefCell;
+use easy_signals::{SignalObserver, SignalSubject};
+
+#[derive(SignalSubject)]
+struct Subject {
+    #[observers]
+    first: Vec<Rc<RefCell<dyn SignalObserver>>>,
+    #[observers]
+    second: Vec<Rc<RefCell<dyn SignalObserver>>>
+}
+
+fn main() {}
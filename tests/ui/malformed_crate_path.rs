@@ -0,0 +1,12 @@
+use std::rc::Rc;
+use std::cell::RefCell;
+use easy_signals::{SignalObserver, SignalSubject};
+
+#[derive(SignalSubject)]
+#[signal_subject(crate)]
+struct Subject {
+    #[observers]
+    subs: Vec<Rc<RefCell<dyn SignalObserver>>>
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use std::rc::Rc;
+use std::cell::RefCell;
+use easy_signals::{SignalObserver, SignalSubject};
+
+#[derive(SignalSubject)]
+struct Subject {
+    subs: Vec<Rc<RefCell<dyn SignalObserver>>>
+}
+
+fn main() {}
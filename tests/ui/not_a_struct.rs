@@ -0,0 +1,8 @@
+use easy_signals::SignalSubject;
+
+#[derive(SignalSubject)]
+enum Subject {
+    Empty
+}
+
+fn main() {}